@@ -0,0 +1,227 @@
+use crate::separation_deg::{bfs, HeapEntry};
+use hashbrown::{HashMap, HashSet};
+use std::collections::{BinaryHeap, VecDeque};
+
+// Degree centrality is a node's degree divided by the most neighbors it could have, N - 1. It is the simplest
+// centrality and a baseline for the others. Nodes are ranked from most to least central, with ties in ascending id
+// order. A single-node graph has nothing to connect to, so its node scores 0.0.
+pub fn degree_centrality(adj_list: &HashMap<i32, HashSet<i32>>) -> Vec<(i32, f64)> {
+    let possible_neighbors = adj_list.len().saturating_sub(1);
+    let mut ranking: Vec<(i32, f64)> = adj_list.iter()
+        .map(|(&node, neighbors)| {
+            let centrality = if possible_neighbors == 0 { 0.0 } else { neighbors.len() as f64 / possible_neighbors as f64 };
+            (node, centrality)
+        })
+        .collect();
+
+    ranking.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranking
+}
+
+// Betweenness centrality measures how often a node lies on the shortest paths between other pairs of nodes.
+// Computed with Brandes' algorithm: a BFS from every source counts shortest paths (sigma), then dependencies are
+// accumulated back along the shortest-path DAG in reverse BFS order.
+// With `normalized` set, scores are divided by the number of pairs not involving the node, (n-1)(n-2)/2.
+pub fn betweenness_centrality(adj_list: &HashMap<i32, HashSet<i32>>, normalized: bool) -> HashMap<i32, f64> {
+    let mut centrality: HashMap<i32, f64> = adj_list.keys().map(|&node| (node, 0.0)).collect();
+
+    for &source in adj_list.keys() {
+        let mut stack: Vec<i32> = Vec::new();
+        let mut predecessors: HashMap<i32, Vec<i32>> = HashMap::new();
+        let mut sigma: HashMap<i32, f64> = HashMap::new();
+        let mut distances: HashMap<i32, i32> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        sigma.insert(source, 1.0);
+        distances.insert(source, 0);
+        queue.push_back(source);
+
+        // Single-source shortest paths, counting how many shortest paths reach each node.
+        while let Some(current) = queue.pop_front() {
+            stack.push(current);
+            let current_distance = distances[&current];
+            let current_sigma = sigma[&current];
+
+            if let Some(neighbors) = adj_list.get(&current) {
+                for &neighbor in neighbors {
+                    if !distances.contains_key(&neighbor) {
+                        distances.insert(neighbor, current_distance + 1);
+                        queue.push_back(neighbor);
+                    }
+                    if distances[&neighbor] == current_distance + 1 {
+                        *sigma.entry(neighbor).or_insert(0.0) += current_sigma;
+                        predecessors.entry(neighbor).or_default().push(current);
+                    }
+                }
+            }
+        }
+
+        accumulate_dependencies(source, stack, &predecessors, &sigma, &mut centrality);
+    }
+
+    // Every undirected pair was counted once from each endpoint.
+    let n = centrality.len() as f64;
+    let scale = if normalized && n > 2.0 { 1.0 / ((n - 1.0) * (n - 2.0)) } else { 0.5 };
+    for score in centrality.values_mut() {
+        *score *= scale;
+    }
+
+    centrality
+}
+
+// Betweenness centrality on a weighted graph, where shortest paths are the ones with the smallest total distance.
+// Brandes' algorithm with Dijkstra in place of BFS: nodes are settled in order of distance, and a node's path count
+// is final by the time it is settled. Negative and NaN weights are skipped, as in `dijkstra`. Scores are raw (not
+// normalized) and, like `betweenness_centrality`, halved since every undirected pair is counted from both ends.
+pub fn weighted_betweenness_centrality(adj_list: &HashMap<i32, HashMap<i32, f64>>) -> HashMap<i32, f64> {
+    let mut centrality: HashMap<i32, f64> = adj_list.keys().map(|&node| (node, 0.0)).collect();
+
+    for &source in adj_list.keys() {
+        let mut stack: Vec<i32> = Vec::new();
+        let mut predecessors: HashMap<i32, Vec<i32>> = HashMap::new();
+        let mut sigma: HashMap<i32, f64> = HashMap::new();
+        let mut distances: HashMap<i32, f64> = HashMap::new();
+        let mut settled: HashSet<i32> = HashSet::new();
+        let mut heap = BinaryHeap::new();
+
+        sigma.insert(source, 1.0);
+        distances.insert(source, 0.0);
+        heap.push(HeapEntry { distance: 0.0, node: source });
+
+        // Single-source weighted shortest paths, counting how many shortest paths reach each node.
+        while let Some(HeapEntry { distance, node: current }) = heap.pop() {
+            // Skip stale entries left in the heap after a shorter distance was found.
+            if distance > distances[&current] || !settled.insert(current) {
+                continue;
+            }
+            stack.push(current);
+            let current_sigma = sigma[&current];
+
+            let Some(neighbors) = adj_list.get(&current) else {
+                continue;
+            };
+            for (&neighbor, &weight) in neighbors {
+                // A settled node's path count has already been passed on, so a zero-weight edge back to it is ignored.
+                if weight.is_nan() || weight < 0.0 || settled.contains(&neighbor) {
+                    continue;
+                }
+                let candidate = distance + weight;
+                match distances.get(&neighbor) {
+                    Some(&known) if candidate > known => {}
+                    Some(&known) if candidate == known => {
+                        *sigma.entry(neighbor).or_insert(0.0) += current_sigma;
+                        predecessors.entry(neighbor).or_default().push(current);
+                    }
+                    _ => {
+                        // A strictly shorter route replaces every path counted so far.
+                        distances.insert(neighbor, candidate);
+                        sigma.insert(neighbor, current_sigma);
+                        predecessors.insert(neighbor, vec![current]);
+                        heap.push(HeapEntry { distance: candidate, node: neighbor });
+                    }
+                }
+            }
+        }
+
+        accumulate_dependencies(source, stack, &predecessors, &sigma, &mut centrality);
+    }
+
+    for score in centrality.values_mut() {
+        *score *= 0.5;
+    }
+
+    centrality
+}
+
+// The second half of Brandes' algorithm, shared by the unweighted and weighted variants: walk the nodes from farthest
+// to nearest (`stack` holds them in the order they were reached) and add each node's dependency to its score.
+fn accumulate_dependencies(
+    source: i32,
+    mut stack: Vec<i32>,
+    predecessors: &HashMap<i32, Vec<i32>>,
+    sigma: &HashMap<i32, f64>,
+    centrality: &mut HashMap<i32, f64>,
+) {
+    let mut delta: HashMap<i32, f64> = HashMap::new();
+    while let Some(node) = stack.pop() {
+        let node_delta = delta.get(&node).copied().unwrap_or(0.0);
+        if let Some(node_predecessors) = predecessors.get(&node) {
+            for &predecessor in node_predecessors {
+                let share = sigma[&predecessor] / sigma[&node] * (1.0 + node_delta);
+                *delta.entry(predecessor).or_insert(0.0) += share;
+            }
+        }
+        if node != source {
+            *centrality.entry(node).or_insert(0.0) += node_delta;
+        }
+    }
+}
+
+// Closeness centrality is the number of nodes a node can reach divided by the sum of its distances to them.
+// The Wasserman-Faust correction scales this by the fraction of the graph that is reachable, (reachable / (n-1)),
+// so that nodes in small components aren't ranked above well-connected nodes in the giant component.
+// Nodes that reach nothing have a closeness of 0.0.
+pub fn closeness_centrality(adj_list: &HashMap<i32, HashSet<i32>>) -> HashMap<i32, f64> {
+    let n = adj_list.len();
+
+    adj_list.keys()
+        .map(|&node| {
+            let distances = bfs(adj_list, node);
+            let reachable = distances.len() - 1;
+            let total_distance: i64 = distances.values().map(|&distance| distance as i64).sum();
+            if reachable == 0 || total_distance == 0 {
+                return (node, 0.0);
+            }
+            let closeness = reachable as f64 / total_distance as f64;
+            (node, closeness * reachable as f64 / (n - 1) as f64)
+        })
+        .collect()
+}
+
+// Harmonic centrality is the sum of the reciprocals of a node's distances to every other node.
+// Unreachable nodes are infinitely far away and contribute 0, so unlike closeness it needs no correction
+// for disconnected graphs.
+pub fn harmonic_centrality(adj_list: &HashMap<i32, HashSet<i32>>) -> HashMap<i32, f64> {
+    adj_list.keys()
+        .map(|&node| {
+            let harmonic: f64 = bfs(adj_list, node).values()
+                .filter(|&&distance| distance > 0)
+                .map(|&distance| 1.0 / distance as f64)
+                .sum();
+            (node, harmonic)
+        })
+        .collect()
+}
+
+// PageRank ranks nodes by the stationary probability of a random walker who follows an edge with probability `damping`
+// and otherwise teleports to a uniformly random node. Every node starts at 1/N and the scores always sum to 1.
+// Dangling nodes (no outgoing edges) spread their rank uniformly across the whole graph.
+pub fn pagerank(adj_list: &HashMap<i32, HashSet<i32>>, damping: f64, iterations: usize) -> HashMap<i32, f64> {
+    // Include nodes that only appear as neighbors so no rank leaks out of the graph.
+    let nodes: HashSet<i32> = adj_list.keys().chain(adj_list.values().flatten()).copied().collect();
+    let n = nodes.len() as f64;
+    let mut ranks: HashMap<i32, f64> = nodes.iter().map(|&node| (node, 1.0 / n)).collect();
+
+    for _ in 0..iterations {
+        let dangling_rank: f64 = nodes.iter()
+            .filter(|node| adj_list.get(*node).is_none_or(|neighbors| neighbors.is_empty()))
+            .map(|node| ranks[node])
+            .sum();
+        let base = (1.0 - damping) / n + damping * dangling_rank / n;
+        let mut next_ranks: HashMap<i32, f64> = nodes.iter().map(|&node| (node, base)).collect();
+
+        for (node, neighbors) in adj_list {
+            if neighbors.is_empty() {
+                continue;
+            }
+            let share = damping * ranks[node] / neighbors.len() as f64;
+            for neighbor in neighbors {
+                *next_ranks.get_mut(neighbor).unwrap() += share;
+            }
+        }
+
+        ranks = next_ranks;
+    }
+
+    ranks
+}
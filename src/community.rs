@@ -0,0 +1,134 @@
+use hashbrown::{HashMap, HashSet};
+
+// Partition the graph into communities with the Louvain method, which greedily maximizes modularity.
+// Each round moves single nodes into the neighboring community that raises modularity most, then merges
+// every community into one node of a smaller weighted graph and repeats, until no move helps.
+// Community ids are dense, numbered in order of each community's smallest node id.
+pub fn louvain_communities(adj_list: &HashMap<i32, HashSet<i32>>) -> HashMap<i32, usize> {
+    let mut nodes: Vec<i32> = adj_list.keys().copied().collect();
+    nodes.sort_unstable();
+    let index: HashMap<i32, usize> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+
+    // Self-loops are ignored, as they never affect which community a node joins.
+    let mut graph: Vec<HashMap<usize, f64>> = nodes.iter()
+        .map(|node| adj_list[node].iter()
+            .filter(|&neighbor| neighbor != node)
+            .filter_map(|neighbor| index.get(neighbor))
+            .map(|&neighbor| (neighbor, 1.0))
+            .collect())
+        .collect();
+
+    // Which node of the current (aggregated) graph each original node belongs to.
+    let mut membership: Vec<usize> = (0..nodes.len()).collect();
+    loop {
+        let (communities, count) = local_moving(&graph);
+        if count == graph.len() {
+            break;
+        }
+        for member in membership.iter_mut() {
+            *member = communities[*member];
+        }
+        graph = aggregate(&graph, &communities, count);
+    }
+
+    // Nodes are visited in ascending order, so first-seen numbering follows each community's smallest node.
+    let mut renumbered: HashMap<usize, usize> = HashMap::new();
+    nodes.iter().zip(membership)
+        .map(|(&node, member)| {
+            let next_id = renumbered.len();
+            (node, *renumbered.entry(member).or_insert(next_id))
+        })
+        .collect()
+}
+
+// Repeatedly move each node to the neighboring community with the largest modularity gain until no node moves.
+// Returns each node's community, numbered densely, and the number of communities.
+fn local_moving(graph: &[HashMap<usize, f64>]) -> (Vec<usize>, usize) {
+    let degrees: Vec<f64> = graph.iter().map(|neighbors| neighbors.values().sum()).collect();
+    let total_degree: f64 = degrees.iter().sum();
+    let mut community: Vec<usize> = (0..graph.len()).collect();
+    let mut community_degree = degrees.clone();
+
+    let mut moved = total_degree > 0.0;
+    while moved {
+        moved = false;
+        for node in 0..graph.len() {
+            let current = community[node];
+            community_degree[current] -= degrees[node];
+
+            let mut links: HashMap<usize, f64> = HashMap::new();
+            for (&neighbor, &weight) in &graph[node] {
+                if neighbor != node {
+                    *links.entry(community[neighbor]).or_insert(0.0) += weight;
+                }
+            }
+            let mut candidates: Vec<(usize, f64)> = links.iter().map(|(&c, &weight)| (c, weight)).collect();
+            candidates.sort_unstable_by_key(|&(c, _)| c);
+
+            // The gain of joining community `c`, up to a constant factor shared by all candidates.
+            let gain = |c: usize, weight: f64| weight - community_degree[c] * degrees[node] / total_degree;
+            let mut best = current;
+            let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+            for (c, weight) in candidates {
+                // Only move for a real improvement, so rounding noise can't make nodes swap back and forth.
+                if gain(c, weight) > best_gain + 1e-12 {
+                    best = c;
+                    best_gain = gain(c, weight);
+                }
+            }
+
+            community_degree[best] += degrees[node];
+            if best != current {
+                community[node] = best;
+                moved = true;
+            }
+        }
+    }
+
+    let mut renumbered: HashMap<usize, usize> = HashMap::new();
+    for c in community.iter_mut() {
+        let next_id = renumbered.len();
+        *c = *renumbered.entry(*c).or_insert(next_id);
+    }
+    (community, renumbered.len())
+}
+
+// Collapse each community into a single node. Edges inside a community become a self-loop carrying their total weight.
+fn aggregate(graph: &[HashMap<usize, f64>], communities: &[usize], count: usize) -> Vec<HashMap<usize, f64>> {
+    let mut aggregated: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+    for (node, neighbors) in graph.iter().enumerate() {
+        for (&neighbor, &weight) in neighbors {
+            *aggregated[communities[node]].entry(communities[neighbor]).or_insert(0.0) += weight;
+        }
+    }
+    aggregated
+}
+
+// The modularity Q of a partition: the fraction of edges inside communities, minus the fraction expected if
+// edges were placed at random between nodes of the same degrees. Ranges from -0.5 to 1; higher means denser communities.
+// Nodes missing from `communities` are treated as sharing a community with no one. Self-loops are ignored.
+pub fn modularity(adj_list: &HashMap<i32, HashSet<i32>>, communities: &HashMap<i32, usize>) -> f64 {
+    let mut inside_entries = 0;
+    let mut total_degree = 0;
+    let mut community_degree: HashMap<usize, usize> = HashMap::new();
+
+    for (node, neighbors) in adj_list {
+        let community = communities.get(node);
+        for neighbor in neighbors.iter().filter(|&neighbor| neighbor != node) {
+            total_degree += 1;
+            if community.is_some() && communities.get(neighbor) == community {
+                inside_entries += 1;
+            }
+        }
+        if let Some(&community) = community {
+            *community_degree.entry(community).or_insert(0) += neighbors.iter().filter(|&neighbor| neighbor != node).count();
+        }
+    }
+    if total_degree == 0 {
+        return 0.0;
+    }
+
+    let total_degree = total_degree as f64;
+    let expected: f64 = community_degree.values().map(|&degree| (degree as f64 / total_degree).powi(2)).sum();
+    inside_entries as f64 / total_degree - expected
+}
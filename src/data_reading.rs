@@ -1,22 +1,306 @@
-use csv::ReaderBuilder;
+use crate::graph::AdjacencyList;
+use csv::{ReaderBuilder, StringRecord};
+use flate2::read::GzDecoder;
 use hashbrown::{HashMap, HashSet};
+use std::collections::BTreeMap;
 use std::error::Error;
+use std::fmt;
 use std::fs::File;
+use std::io::Read;
+use std::str::FromStr;
+
+// A field in the CSV that couldn't be parsed, with the 1-based line it was found on and its raw contents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvParseError {
+    pub line: u64,
+    pub field: String,
+    pub expected: &'static str,
+}
+
+impl fmt::Display for CsvParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: failed to parse '{}' as {}", self.line, self.field, self.expected)
+    }
+}
+
+impl Error for CsvParseError {}
+
+// Parse one field of a record, reporting the line number and raw contents if it is missing or malformed.
+fn parse_field<T: FromStr>(record: &StringRecord, column: usize, expected: &'static str) -> Result<T, CsvParseError> {
+    let field = record.get(column).unwrap_or("");
+    field.trim().parse().map_err(|_| CsvParseError {
+        line: record.position().map_or(0, |position| position.line()),
+        field: field.to_string(),
+        expected,
+    })
+}
+
+// Like `parse_field` for a number, but NaN and infinite values are rejected too.
+fn parse_finite_field(record: &StringRecord, column: usize, expected: &'static str) -> Result<f64, CsvParseError> {
+    let value: f64 = parse_field(record, column, expected)?;
+    if !value.is_finite() {
+        return Err(CsvParseError {
+            line: record.position().map_or(0, |position| position.line()),
+            field: record.get(column).unwrap_or("").to_string(),
+            expected,
+        });
+    }
+    Ok(value)
+}
+
+// Parse the two node ids at the start of an edge record.
+fn parse_edge(record: &StringRecord) -> Result<(i32, i32), CsvParseError> {
+    Ok((parse_field(record, 0, "node id")?, parse_field(record, 1, "node id")?))
+}
+
+// Settings for reading an edge list. The defaults match the euroroad file: comma-separated, no header row, undirected.
+// Self-loops (rows like `5,5`) are kept by default; set `drop_self_loops` to filter them out during import.
+// The endpoints are read from the first two columns unless `source_column` and `target_column` (0-based) say otherwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub has_headers: bool,
+    pub directed: bool,
+    pub drop_self_loops: bool,
+    pub source_column: usize,
+    pub target_column: usize,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions { delimiter: b',', has_headers: false, directed: false, drop_self_loops: false, source_column: 0, target_column: 1 }
+    }
+}
 
 // This function reads a CSV file where each line represents an edge in a graph and constructs an adjacency list, which is a common way to represent graphs.
 /// The graph is undirected, so an edge from `city1` to `city2` implies an edge back from `city2` to `city1`.
-pub fn build_adjacency_list_from_csv(file_path: &str) -> Result<HashMap<i32, HashSet<i32>>, Box<dyn Error>> {
+pub fn build_adjacency_list_from_csv(file_path: &str) -> Result<AdjacencyList, Box<dyn Error>> {
+    build_adjacency_list_from_csv_with_options(file_path, &CsvOptions::default())
+}
+
+// Read a CSV edge list as a directed graph: a row `city1,city2` only creates the edge `city1 -> city2`, e.g. a one-way road.
+// The target still gets a (possibly empty) entry so that every node appears as a key.
+pub fn build_directed_adjacency_list_from_csv(file_path: &str) -> Result<AdjacencyList, Box<dyn Error>> {
+    build_adjacency_list_from_csv_with_options(file_path, &CsvOptions { directed: true, ..CsvOptions::default() })
+}
+
+// Read an edge list with a custom delimiter (e.g. `b'\t'` for TSV files), an optional header row, and optional direction.
+pub fn build_adjacency_list_from_csv_with_options(file_path: &str, options: &CsvOptions) -> Result<AdjacencyList, Box<dyn Error>> {
+    Ok(build_adjacency_list_from_csv_with_self_loop_count(file_path, options)?.0)
+}
+
+// Read an edge list whose endpoints are in the given 0-based columns, e.g. columns 1 and 2 of `id,city1,city2,year_built,length`.
+// The other columns are ignored.
+pub fn build_adjacency_list_from_csv_cols(file_path: &str, src_col: usize, dst_col: usize) -> Result<AdjacencyList, Box<dyn Error>> {
+    build_adjacency_list_from_csv_with_options(file_path, &CsvOptions { source_column: src_col, target_column: dst_col, ..CsvOptions::default() })
+}
+
+// An adjacency list together with the number of self-loop rows dropped while reading it.
+pub type AdjacencyListWithSelfLoopCount = (AdjacencyList, usize);
+
+// Like `build_adjacency_list_from_csv_with_options`, but also returns how many self-loop rows were dropped
+// (always 0 unless `drop_self_loops` is set). A dropped self-loop still registers its node.
+// Files ending in `.gz` are decompressed on the fly.
+pub fn build_adjacency_list_from_csv_with_self_loop_count(file_path: &str, options: &CsvOptions) -> Result<AdjacencyListWithSelfLoopCount, Box<dyn Error>> {
+    let file = File::open(file_path)?;
+    if file_path.ends_with(".gz") {
+        read_edge_list(GzDecoder::new(file), options)
+    } else {
+        read_edge_list(file, options)
+    }
+}
+
+// Read a gzip-compressed CSV edge list (e.g. `euroroad.csv.gz`), regardless of the file extension.
+pub fn build_adjacency_list_from_csv_gz(file_path: &str) -> Result<AdjacencyList, Box<dyn Error>> {
+    build_adjacency_list_from_reader(GzDecoder::new(File::open(file_path)?), false)
+}
+
+// Build an adjacency list from any source of CSV edge-list data, such as stdin, a decompressor or an in-memory buffer.
+// Records are read one at a time, so the whole input never has to be held in memory.
+pub fn build_adjacency_list_from_reader<R: Read>(reader: R, has_headers: bool) -> Result<AdjacencyList, Box<dyn Error>> {
+    Ok(read_edge_list(reader, &CsvOptions { has_headers, ..CsvOptions::default() })?.0)
+}
+
+fn read_edge_list<R: Read>(reader: R, options: &CsvOptions) -> Result<AdjacencyListWithSelfLoopCount, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.has_headers)
+        .from_reader(reader);
+    let mut adjacency_list: AdjacencyList = HashMap::new();
+    let mut dropped_self_loops = 0;
+
+    for result in rdr.records() {
+        let record = result?;
+        let city1: i32 = parse_field(&record, options.source_column, "node id")?;
+        let city2: i32 = parse_field(&record, options.target_column, "node id")?;
+
+        if options.drop_self_loops && city1 == city2 {
+            adjacency_list.entry(city1).or_default();
+            dropped_self_loops += 1;
+            continue;
+        }
+
+        adjacency_list.entry(city1).or_default().insert(city2);
+        if options.directed {
+            adjacency_list.entry(city2).or_default();
+        } else {
+            adjacency_list.entry(city2).or_default().insert(city1);
+        }
+    }
+
+    Ok((adjacency_list, dropped_self_loops))
+}
+
+// Read a CSV edge list whose rows name the two endpoints, e.g. `Paris,Lyon`, keeping the labels as strings.
+pub fn build_labeled_edge_list_from_csv(file_path: &str) -> Result<Vec<(String, String)>, Box<dyn Error>> {
     let mut rdr = ReaderBuilder::new().has_headers(false).from_reader(File::open(file_path)?);
-    let mut adjacency_list: HashMap<i32, HashSet<i32>> = HashMap::new();
+    let mut edges: Vec<(String, String)> = Vec::new();
+
+    for result in rdr.records() {
+        let record = result?;
+        edges.push((parse_field(&record, 0, "node label")?, parse_field(&record, 1, "node label")?));
+    }
+
+    Ok(edges)
+}
+
+// An adjacency list together with a warning for each row that was skipped while reading it.
+pub type AdjacencyListWithWarnings = (AdjacencyList, Vec<String>);
+
+// Like `build_adjacency_list_from_csv`, but rows that can't be parsed are skipped instead of aborting the import.
+// Returns the graph built from the valid rows together with a warning describing each skipped row.
+pub fn build_adjacency_list_from_csv_lenient(file_path: &str) -> Result<AdjacencyListWithWarnings, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new().has_headers(false).flexible(true).from_reader(File::open(file_path)?);
+    let mut adjacency_list: AdjacencyList = HashMap::new();
+    let mut warnings: Vec<String> = Vec::new();
+
+    for result in rdr.records() {
+        let record = match result {
+            Ok(record) => record,
+            // Failing to read the file at all is still fatal.
+            Err(error) if error.is_io_error() => return Err(error.into()),
+            Err(error) => {
+                warnings.push(error.to_string());
+                continue;
+            }
+        };
+        let (city1, city2) = match parse_edge(&record) {
+            Ok(edge) => edge,
+            Err(error) => {
+                warnings.push(error.to_string());
+                continue;
+            }
+        };
+
+        adjacency_list.entry(city1).or_default().insert(city2);
+        adjacency_list.entry(city2).or_default().insert(city1);
+    }
+
+    Ok((adjacency_list, warnings))
+}
+
+// Like `build_adjacency_list_from_csv`, but reads an optional third column as the edge weight (e.g. `city1,city2,distance_km`).
+// Rows with only two columns default to a weight of 1.0. The weight is stored in both directions since the graph is undirected.
+pub fn build_weighted_adjacency_list_from_csv(file_path: &str) -> Result<HashMap<i32, HashMap<i32, f64>>, Box<dyn Error>> {
+    read_weighted_edge_list(file_path, 1.0)
+}
+
+// Like `build_weighted_adjacency_list_from_csv`, for files whose weights are whole numbers (e.g. distances in meters).
+// The weights are kept as exact integers, so comparing and tie-breaking them has no floating-point rounding.
+// Rows with only two columns default to a weight of 1; a fractional weight such as `2.5` is a parse error.
+pub fn build_integer_weighted_adjacency_list_from_csv(file_path: &str) -> Result<HashMap<i32, HashMap<i32, i64>>, Box<dyn Error>> {
+    read_weighted_edge_list(file_path, 1)
+}
+
+// The reader behind both weighted variants; `default_weight` is used for rows without a third column.
+fn read_weighted_edge_list<W: FromStr + Copy>(file_path: &str, default_weight: W) -> Result<HashMap<i32, HashMap<i32, W>>, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new().has_headers(false).flexible(true).from_reader(File::open(file_path)?);
+    let mut adjacency_list: HashMap<i32, HashMap<i32, W>> = HashMap::new();
+
+    for result in rdr.records() {
+        let record = result?;
+        let (city1, city2) = parse_edge(&record)?;
+        let weight: W = if record.len() > 2 { parse_field(&record, 2, "edge weight")? } else { default_weight };
+
+        adjacency_list.entry(city1).or_default().insert(city2, weight);
+        adjacency_list.entry(city2).or_default().insert(city1, weight);
+    }
+
+    Ok(adjacency_list)
+}
+
+// Read a file that declares nodes and edges together: a row with a single field (`42`) declares a node, and a row
+// with two fields (`42,57`) adds an undirected edge. Declared nodes get an entry even if no edge mentions them,
+// so isolated nodes survive the import. Any columns after the first two are ignored.
+pub fn build_adjacency_list_from_nodes_and_edges_csv(file_path: &str) -> Result<AdjacencyList, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new().has_headers(false).flexible(true).from_reader(File::open(file_path)?);
+    let mut adjacency_list: AdjacencyList = HashMap::new();
 
     for result in rdr.records() {
         let record = result?;
-        let city1: i32 = record[0].parse()?;
-        let city2: i32 = record[1].parse()?;
+        if record.len() == 1 {
+            adjacency_list.entry(parse_field(&record, 0, "node id")?).or_default();
+            continue;
+        }
 
+        let (city1, city2) = parse_edge(&record)?;
         adjacency_list.entry(city1).or_default().insert(city2);
         adjacency_list.entry(city2).or_default().insert(city1);
     }
 
     Ok(adjacency_list)
 }
+
+// Read a JSON object mapping each node id to an array of its neighbor ids, e.g. `{"1": [2, 3], "2": [1]}`.
+// Missing back-edges are added, so `3` above also gets `1` as a neighbor, and nodes with an empty array are kept.
+pub fn build_adjacency_list_from_json(file_path: &str) -> Result<AdjacencyList, Box<dyn Error>> {
+    let contents = std::fs::read_to_string(file_path)?;
+    let nodes: BTreeMap<String, Vec<i32>> = serde_json::from_str(&contents)?;
+    let mut adjacency_list: AdjacencyList = HashMap::new();
+
+    for (key, neighbors) in nodes {
+        // Keys must be exactly a node id, so `"1 "` is rejected rather than read as node 1.
+        let node: i32 = key.parse().map_err(|_| format!("invalid node id {:?} in JSON adjacency list", key))?;
+        adjacency_list.entry(node).or_default();
+        for neighbor in neighbors {
+            adjacency_list.entry(node).or_default().insert(neighbor);
+            adjacency_list.entry(neighbor).or_default().insert(node);
+        }
+    }
+
+    Ok(adjacency_list)
+}
+
+// Read a square adjacency matrix (e.g. 0/1 entries) where a nonzero value at row i, column j means an edge between
+// node i and node j. Node ids are the 0-based row/column indices, and every node gets an entry even if it has no edges.
+// The matrix is symmetrized, so an edge in either triangle creates an undirected edge. NaN and infinite entries are
+// rejected rather than read as edges.
+pub fn build_adjacency_list_from_matrix_csv(file_path: &str) -> Result<AdjacencyList, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new().has_headers(false).flexible(true).from_reader(File::open(file_path)?);
+    let mut rows: Vec<Vec<f64>> = Vec::new();
+
+    for result in rdr.records() {
+        let record = result?;
+        let row = (0..record.len())
+            .map(|column| parse_finite_field(&record, column, "finite matrix entry"))
+            .collect::<Result<Vec<f64>, _>>()?;
+        rows.push(row);
+    }
+
+    let size = rows.len();
+    if let Some((index, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != size) {
+        return Err(format!("matrix is not square: row {} has {} columns but there are {} rows", index + 1, row.len(), size).into());
+    }
+
+    let mut adjacency_list: AdjacencyList = (0..size as i32).map(|node| (node, HashSet::new())).collect();
+    for (i, row) in rows.iter().enumerate() {
+        for (j, &value) in row.iter().enumerate() {
+            if value != 0.0 {
+                adjacency_list.entry(i as i32).or_default().insert(j as i32);
+                adjacency_list.entry(j as i32).or_default().insert(i as i32);
+            }
+        }
+    }
+
+    Ok(adjacency_list)
+}
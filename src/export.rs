@@ -0,0 +1,156 @@
+use crate::centrality::{betweenness_centrality, closeness_centrality};
+use crate::graph::edges;
+use crate::network_stats::local_clustering_coefficient;
+use crate::separation_deg::all_eccentricities;
+use csv::Writer;
+use hashbrown::{HashMap, HashSet};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+// Write a separation distribution to a CSV file with a `degree,percentage` header, sorted by degree ascending.
+pub fn write_distribution_csv(distribution: &BTreeMap<i32, f64>, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    writer.write_record(["degree", "percentage"])?;
+    for (degree, percentage) in distribution {
+        writer.write_record([degree.to_string(), percentage.to_string()])?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+// Write one row per node, sorted by node id, with its degree, eccentricity, closeness, (raw) betweenness and local
+// clustering coefficient. Each metric is computed once for the whole graph before any row is written.
+pub fn write_node_metrics_csv(adj_list: &HashMap<i32, HashSet<i32>>, path: &str) -> Result<(), Box<dyn Error>> {
+    let eccentricities = all_eccentricities(adj_list);
+    let closeness = closeness_centrality(adj_list);
+    let betweenness = betweenness_centrality(adj_list, false);
+    let mut nodes: Vec<i32> = adj_list.keys().copied().collect();
+    nodes.sort();
+
+    let mut writer = Writer::from_path(path)?;
+    writer.write_record(["node", "degree", "eccentricity", "closeness", "betweenness", "clustering"])?;
+    for node in nodes {
+        writer.write_record([
+            node.to_string(),
+            adj_list[&node].len().to_string(),
+            eccentricities[&node].to_string(),
+            closeness[&node].to_string(),
+            betweenness[&node].to_string(),
+            local_clustering_coefficient(adj_list, node).to_string(),
+        ])?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+// Every node id, including nodes that only appear as neighbors, sorted.
+fn sorted_nodes(adj_list: &HashMap<i32, HashSet<i32>>) -> Vec<i32> {
+    let nodes: HashSet<i32> = adj_list.keys().chain(adj_list.values().flatten()).copied().collect();
+    let mut nodes: Vec<i32> = nodes.into_iter().collect();
+    nodes.sort();
+    nodes
+}
+
+// Write the graph as GraphML (e.g. for Gephi): one `<node>` per id and one `<edge>` per undirected edge,
+// written once with the smaller id as the source.
+pub fn write_graphml(adj_list: &HashMap<i32, HashSet<i32>>, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    writeln!(writer, r#"  <graph id="G" edgedefault="undirected">"#)?;
+    for node in sorted_nodes(adj_list) {
+        writeln!(writer, r#"    <node id="n{}"/>"#, node)?;
+    }
+    for (source, target) in edges(adj_list) {
+        writeln!(writer, r#"    <edge source="n{}" target="n{}"/>"#, source, target)?;
+    }
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+// Write the graph in Graphviz DOT format as `graph { a -- b; ... }`, each undirected edge once.
+// With `include_isolated_nodes` set, nodes without any edges are also written so they show up in the render.
+pub fn write_dot(adj_list: &HashMap<i32, HashSet<i32>>, path: &str, include_isolated_nodes: bool) -> Result<(), Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writeln!(writer, "graph {{")?;
+    if include_isolated_nodes {
+        let mut isolated: Vec<i32> = adj_list.iter()
+            .filter(|(_, neighbors)| neighbors.is_empty())
+            .map(|(&node, _)| node)
+            .collect();
+        isolated.sort();
+        for node in isolated {
+            writeln!(writer, "    {};", node)?;
+        }
+    }
+    for (a, b) in edges(adj_list) {
+        writeln!(writer, "    {} -- {};", a, b)?;
+    }
+    writeln!(writer, "}}")?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+// A per-node metric that `write_dot_with_attribute` can annotate nodes with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeAttribute {
+    Degree,
+    Eccentricity,
+    Betweenness,
+    Closeness,
+}
+
+impl NodeAttribute {
+    // The attribute name written to the DOT file, matching the `write_node_metrics_csv` column.
+    fn name(self) -> &'static str {
+        match self {
+            NodeAttribute::Degree => "degree",
+            NodeAttribute::Eccentricity => "eccentricity",
+            NodeAttribute::Betweenness => "betweenness",
+            NodeAttribute::Closeness => "closeness",
+        }
+    }
+
+    // The attribute's value for every node of the adjacency list.
+    fn values(self, adj_list: &HashMap<i32, HashSet<i32>>) -> HashMap<i32, f64> {
+        match self {
+            NodeAttribute::Degree => adj_list.iter().map(|(&node, neighbors)| (node, neighbors.len() as f64)).collect(),
+            NodeAttribute::Eccentricity => all_eccentricities(adj_list).into_iter().map(|(node, eccentricity)| (node, eccentricity as f64)).collect(),
+            NodeAttribute::Betweenness => betweenness_centrality(adj_list, false),
+            NodeAttribute::Closeness => closeness_centrality(adj_list),
+        }
+    }
+}
+
+// Like `write_dot`, but every node is written with its `attribute` value (e.g. `2 [betweenness=1, width=1.30];`) so
+// Graphviz can render importance visually. `width` grows from 0.3 for a value of 0 to 1.3 for the largest value.
+pub fn write_dot_with_attribute(adj_list: &HashMap<i32, HashSet<i32>>, attribute: NodeAttribute, path: &str) -> Result<(), Box<dyn Error>> {
+    let values = attribute.values(adj_list);
+    let max_value = values.values().copied().fold(0.0, f64::max);
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writeln!(writer, "graph {{")?;
+    writeln!(writer, "    node [shape=circle];")?;
+    for node in sorted_nodes(adj_list) {
+        let value = values.get(&node).copied().unwrap_or(0.0);
+        let width = if max_value > 0.0 { 0.3 + value / max_value } else { 0.3 };
+        writeln!(writer, "    {} [{}={}, width={:.2}];", node, attribute.name(), value, width)?;
+    }
+    for (a, b) in edges(adj_list) {
+        writeln!(writer, "    {} -- {};", a, b)?;
+    }
+    writeln!(writer, "}}")?;
+    writer.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use hashbrown::{HashMap, HashSet};
+
+// A small seeded pseudo-random number generator (SplitMix64). It is not cryptographically secure, but it is fast,
+// well distributed, and gives the same sequence for the same seed on every platform, which is all benchmarks need.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A uniform float in [0, 1), built from the top 53 bits.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// Generate an Erdős–Rényi random graph G(n, p): nodes 0..n, with each of the n(n-1)/2 possible undirected edges
+// present independently with probability `p`. The same seed always produces the same graph.
+// Every node gets an entry, so nodes that drew no edges are kept as isolated nodes.
+pub fn generate_erdos_renyi(n: usize, p: f64, seed: u64) -> HashMap<i32, HashSet<i32>> {
+    let mut rng = SplitMix64::new(seed);
+    let mut adjacency_list: HashMap<i32, HashSet<i32>> = (0..n as i32).map(|node| (node, HashSet::new())).collect();
+
+    for a in 0..n as i32 {
+        for b in a + 1..n as i32 {
+            if rng.next_f64() < p {
+                adjacency_list.get_mut(&a).unwrap().insert(b);
+                adjacency_list.get_mut(&b).unwrap().insert(a);
+            }
+        }
+    }
+
+    adjacency_list
+}
+
+// Generate a `rows` x `cols` lattice where each cell is connected to the cells above, below, left and right of it.
+// The cell in row `r` and column `c` is node `r * cols + c`. Its diameter is (rows - 1) + (cols - 1).
+pub fn generate_grid(rows: usize, cols: usize) -> HashMap<i32, HashSet<i32>> {
+    let mut adjacency_list: HashMap<i32, HashSet<i32>> = HashMap::new();
+    let node = |row: usize, col: usize| (row * cols + col) as i32;
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let neighbors = adjacency_list.entry(node(row, col)).or_default();
+            if row > 0 {
+                neighbors.insert(node(row - 1, col));
+            }
+            if row + 1 < rows {
+                neighbors.insert(node(row + 1, col));
+            }
+            if col > 0 {
+                neighbors.insert(node(row, col - 1));
+            }
+            if col + 1 < cols {
+                neighbors.insert(node(row, col + 1));
+            }
+        }
+    }
+
+    adjacency_list
+}
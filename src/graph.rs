@@ -0,0 +1,176 @@
+use crate::data_reading;
+use hashbrown::{HashMap, HashSet};
+use std::error::Error;
+use std::sync::OnceLock;
+
+// The adjacency list every reader returns and the analysis functions take: each node mapped to its neighbors.
+pub type AdjacencyList = HashMap<i32, HashSet<i32>>;
+
+// An undirected graph backed by an adjacency list.
+// The analysis functions operate on the adjacency list, which can be borrowed with `adjacency_list()`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Graph {
+    adjacency_list: AdjacencyList,
+}
+
+impl Graph {
+    // Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Read a graph from a CSV edge list, exactly as `data_reading::build_adjacency_list_from_csv` does.
+    pub fn from_csv(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::from(data_reading::build_adjacency_list_from_csv(file_path)?))
+    }
+
+    // Add an undirected edge, creating either endpoint if it doesn't exist yet.
+    pub fn add_edge(&mut self, a: i32, b: i32) {
+        self.adjacency_list.entry(a).or_default().insert(b);
+        self.adjacency_list.entry(b).or_default().insert(a);
+    }
+
+    // Union another adjacency list into this graph, e.g. to stitch regional edge lists together.
+    // Every node of `other` is kept, including isolated ones, and edges present in both are stored once.
+    pub fn merge(&mut self, other: &AdjacencyList) {
+        for (&node, neighbors) in other {
+            self.adjacency_list.entry(node).or_default().extend(neighbors.iter().copied());
+        }
+    }
+
+    // Iterate over the direct neighbors of a node. A node that isn't in the graph has no neighbors.
+    pub fn neighbors(&self, node: i32) -> impl Iterator<Item = i32> + '_ {
+        self.adjacency_list.get(&node).into_iter().flatten().copied()
+    }
+
+    // The number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.adjacency_list.len()
+    }
+
+    // Borrow the underlying adjacency list to pass it to the analysis functions.
+    pub fn adjacency_list(&self) -> &AdjacencyList {
+        &self.adjacency_list
+    }
+}
+
+impl From<AdjacencyList> for Graph {
+    fn from(adjacency_list: AdjacencyList) -> Self {
+        Self { adjacency_list }
+    }
+}
+
+// The neighbors of `node`, or an empty set if the node isn't in the adjacency list, so custom analysis code can look
+// up any node without `adj_list[&node]` panicking. Missing nodes all share one static empty set.
+pub fn neighbors(adjacency_list: &AdjacencyList, node: i32) -> &HashSet<i32> {
+    static EMPTY: OnceLock<HashSet<i32>> = OnceLock::new();
+    adjacency_list.get(&node).unwrap_or_else(|| EMPTY.get_or_init(HashSet::new))
+}
+
+// Check that every edge is stored in both directions, as the CSV readers do. Hand-built adjacency lists can miss a
+// back-edge, which breaks the BFS-based metrics that assume an undirected graph. Returns the first `(a, b)`, in
+// ascending order, where `a` lists `b` as a neighbor but `b` doesn't list `a`.
+pub fn assert_symmetric(adjacency_list: &AdjacencyList) -> Result<(), (i32, i32)> {
+    let mut nodes: Vec<i32> = adjacency_list.keys().copied().collect();
+    nodes.sort_unstable();
+
+    for a in nodes {
+        let mut targets: Vec<i32> = adjacency_list[&a].iter().copied().collect();
+        targets.sort_unstable();
+        if let Some(b) = targets.into_iter().find(|b| !neighbors(adjacency_list, *b).contains(&a)) {
+            return Err((a, b));
+        }
+    }
+
+    Ok(())
+}
+
+// Every undirected edge exactly once as `(a, b)` with `a <= b`, sorted, e.g. for exporting or comparing edge sets.
+pub fn edges(adjacency_list: &AdjacencyList) -> Vec<(i32, i32)> {
+    let mut edges: Vec<(i32, i32)> = adjacency_list.iter()
+        .flat_map(|(&a, neighbors)| neighbors.iter().filter(move |&&b| a <= b).map(move |&b| (a, b)))
+        .collect();
+    edges.sort_unstable();
+    edges
+}
+
+// Renumber the nodes 0..N in ascending id order and return a vector-backed adjacency list, plus the original id of each
+// index. Sparse ids like 1, 5000 and 99999 then index straight into a `Vec` instead of going through a hash map.
+// Each neighbor list is sorted. Nodes that only appear as a neighbor get an index too.
+pub fn compactify(adjacency_list: &AdjacencyList) -> (Vec<Vec<usize>>, Vec<i32>) {
+    let mut ids: Vec<i32> = adjacency_list.keys()
+        .chain(adjacency_list.values().flatten())
+        .copied()
+        .collect::<HashSet<i32>>()
+        .into_iter()
+        .collect();
+    ids.sort_unstable();
+    let index: HashMap<i32, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+    let compact = ids.iter()
+        .map(|id| {
+            let mut neighbors: Vec<usize> = adjacency_list.get(id).into_iter().flatten().map(|neighbor| index[neighbor]).collect();
+            neighbors.sort_unstable();
+            neighbors
+        })
+        .collect();
+
+    (compact, ids)
+}
+
+// A graph whose nodes are named by string labels (e.g. city names). Each label is mapped to a dense integer id,
+// so the integer-based analysis functions can run on `graph()`, and their results translated back with `label_for`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LabeledGraph {
+    graph: Graph,
+    labels: Vec<String>,
+    ids: HashMap<String, i32>,
+}
+
+impl LabeledGraph {
+    // Create an empty labeled graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Read a graph from a CSV of `source_name,target_name` rows.
+    pub fn from_labeled_csv(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut labeled_graph = Self::new();
+        for (source, target) in data_reading::build_labeled_edge_list_from_csv(file_path)? {
+            labeled_graph.add_edge(&source, &target);
+        }
+        Ok(labeled_graph)
+    }
+
+    // Add an undirected edge between two labels, assigning ids to labels seen for the first time.
+    pub fn add_edge(&mut self, a: &str, b: &str) {
+        let (a, b) = (self.intern(a), self.intern(b));
+        self.graph.add_edge(a, b);
+    }
+
+    // The id assigned to a label, if the label is in the graph.
+    pub fn id_for(&self, label: &str) -> Option<i32> {
+        self.ids.get(label).copied()
+    }
+
+    // The label an id was assigned to, if the id is in the graph.
+    pub fn label_for(&self, id: i32) -> Option<&str> {
+        usize::try_from(id).ok().and_then(|index| self.labels.get(index)).map(String::as_str)
+    }
+
+    // The integer-id graph, to pass to the analysis functions.
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    // Ids are handed out in order of first appearance: 0, 1, 2, ...
+    fn intern(&mut self, label: &str) -> i32 {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+        let id = self.labels.len() as i32;
+        self.labels.push(label.to_string());
+        self.ids.insert(label.to_string(), id);
+        id
+    }
+}
@@ -0,0 +1,1549 @@
+// The road network analysis library. The `final_project` binary is a thin command-line wrapper around it.
+pub mod centrality;
+pub mod community;
+pub mod data_reading;
+pub mod export;
+pub mod generators;
+pub mod graph;
+pub mod network_stats;
+pub mod report;
+pub mod separation_deg;
+pub mod structure;
+
+// Unit test
+#[cfg(test)]
+mod tests {
+    use super::centrality::{betweenness_centrality, closeness_centrality, harmonic_centrality, pagerank};
+    use super::centrality::{weighted_betweenness_centrality, degree_centrality};
+    use super::data_reading::{build_adjacency_list_from_csv, build_weighted_adjacency_list_from_csv, CsvParseError};
+    use super::data_reading::build_directed_adjacency_list_from_csv;
+    use super::data_reading::build_integer_weighted_adjacency_list_from_csv;
+    use super::data_reading::build_adjacency_list_from_csv_lenient;
+    use super::data_reading::build_adjacency_list_from_matrix_csv;
+    use super::data_reading::{build_adjacency_list_from_csv_with_options, CsvOptions};
+    use super::data_reading::build_adjacency_list_from_csv_with_self_loop_count;
+    use super::data_reading::{build_adjacency_list_from_csv_cols, build_adjacency_list_from_nodes_and_edges_csv};
+    use super::data_reading::build_adjacency_list_from_json;
+    use super::data_reading::{build_adjacency_list_from_reader, build_adjacency_list_from_csv_gz};
+    use super::generators::{generate_erdos_renyi, generate_grid};
+    use super::community::{louvain_communities, modularity};
+    use super::export::{write_distribution_csv, write_graphml, write_dot, write_node_metrics_csv};
+    use super::export::{write_dot_with_attribute, NodeAttribute};
+    use super::graph::{Graph, LabeledGraph, compactify, edges, neighbors, assert_symmetric};
+    use super::report::{compute_report, compute_report_with_progress, compute_report_timed};
+    use super::network_stats::{calculate_degree_distribution, average_node_degree, max_node_degree};
+    use super::network_stats::{local_clustering_coefficient, global_clustering_coefficient, count_triangles};
+    use super::network_stats::{edge_count, node_count, density, degree_assortativity};
+    use super::network_stats::{node_strength, strength_distribution, isolated_nodes, jaccard_similarity};
+    use super::network_stats::{common_neighbors, friendship_paradox_ratio};
+    use super::separation_deg::calculate_normalized_separation_distribution;
+    use super::structure::{k_core, articulation_points, bridges, is_bipartite, has_cycle, minimum_spanning_tree};
+    use super::structure::{subgraph, remove_node, strongly_connected_components, ConnectivityTracker};
+    use super::structure::maximum_bipartite_matching;
+    use super::separation_deg::calculate_connected_components;
+    use super::separation_deg::{calculate_diameter, calculate_component_diameters, approximate_diameter, diameter_path};
+    use super::separation_deg::{connected_components, largest_connected_component, is_connected, reachable_counts};
+    use super::separation_deg::{eccentricity, radius, graph_center, all_eccentricities};
+    use super::separation_deg::{trimmed_mean_separation, path_length_percentiles, fraction_within_degrees, nodes_at_distance, bfs_limited, bfs_with_horizon, bfs_levels};
+    use super::separation_deg::{bidirectional_bfs, bidirectional_bfs_visited_count, shortest_path_deterministic};
+    use super::separation_deg::{compute_all_distances, calculate_max_degree_of_separation, calculate_max_degree_of_separation_from_distances};
+    use super::separation_deg::{calculate_average_max_degree_from_distances, calculate_average_shortest_path_length_from_distances};
+    use super::separation_deg::{calculate_mean_and_std_dev_from_distances, calculate_normalized_separation_distribution_from_distances};
+    use super::separation_deg::{calculate_average_max_degree, calculate_average_shortest_path_length, calculate_mean_and_std_dev};
+    use super::separation_deg::{bfs, wiener_index};
+    use super::separation_deg::{dijkstra, calculate_weighted_average_path_length, weighted_separation_distribution};
+    use super::separation_deg::{GraphError, compute_all_distances_with_progress, calculate_component_average_path_lengths};
+    use super::separation_deg::shortest_path_length;
+    use super::separation_deg::shortest_path;
+    use super::separation_deg::build_sample_network;
+    use super::separation_deg::build_weighted_sample_network;
+    use super::separation_deg::{build_network_from_edges, build_disconnected_sample, build_cyclic_sample, build_star_sample};
+    use flate2::read::GzDecoder;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use hashbrown::{HashMap, HashSet};
+    use std::collections::BTreeMap;
+    use std::io::Write;
+
+    // Write `contents` to a uniquely named file in the system temp directory and return its path.
+    fn write_temp_file(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("final_project_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_separation_distribution_sums_to_one() {
+        let adjacency_list = build_sample_network();
+        // Calculate the normalized separation distribution which shows how nodes are separated in the network.
+        let (separation_distribution, _, _) = calculate_normalized_separation_distribution(&adjacency_list).unwrap();
+        let sum_of_percentages: f64 = separation_distribution.values().sum();
+        assert!((sum_of_percentages - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_sample_network_is_a_path() {
+        assert_eq!(edges(&build_sample_network()), vec![(1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn test_connected_components_two_disjoint_triangles() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 1), (4, 5), (5, 6), (6, 4)]);
+        assert_eq!(calculate_connected_components(&adjacency_list), 2);
+    }
+
+    #[test]
+    fn test_connected_components_single_chain() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(calculate_connected_components(&adjacency_list), 1);
+    }
+
+    #[test]
+    fn test_dijkstra_matches_hand_computed_distances() {
+        let adjacency_list = build_weighted_sample_network();
+        let distances = dijkstra(&adjacency_list, 1);
+        // 1 -> 3 is cheaper through 2 (1.0 + 2.0) than along the direct 5.0 road.
+        assert_eq!(distances.len(), 3);
+        assert!((distances[&1] - 0.0).abs() < f64::EPSILON);
+        assert!((distances[&2] - 1.0).abs() < f64::EPSILON);
+        assert!((distances[&3] - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_dijkstra_skips_negative_weights() {
+        let mut adjacency_list = build_weighted_sample_network();
+        adjacency_list.entry(1).or_default().insert(4, -1.0);
+        adjacency_list.entry(4).or_default().insert(1, -1.0);
+        let distances = dijkstra(&adjacency_list, 1);
+        assert!(!distances.contains_key(&4));
+    }
+
+    #[test]
+    fn test_weighted_csv_reads_third_column_symmetrically() {
+        let path = write_temp_file("weighted.csv", "1,2,3.5\n2,3,1.25\n");
+        let adjacency_list = build_weighted_adjacency_list_from_csv(&path).unwrap();
+        assert_eq!(adjacency_list[&1][&2], 3.5);
+        assert_eq!(adjacency_list[&2][&1], 3.5);
+        assert_eq!(adjacency_list[&3][&2], 1.25);
+    }
+
+    #[test]
+    fn test_weighted_csv_defaults_missing_weight_to_one() {
+        let path = write_temp_file("weighted_default.csv", "1,2\n2,3,4.0\n");
+        let adjacency_list = build_weighted_adjacency_list_from_csv(&path).unwrap();
+        assert_eq!(adjacency_list[&1][&2], 1.0);
+        assert_eq!(adjacency_list[&2][&3], 4.0);
+    }
+
+    #[test]
+    fn test_weighted_csv_reports_row_of_bad_weight() {
+        let path = write_temp_file("weighted_bad.csv", "1,2,3.0\n2,3,far\n");
+        let error = build_weighted_adjacency_list_from_csv(&path).unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+        assert!(error.to_string().contains("far"));
+    }
+
+    #[test]
+    fn test_integer_weighted_csv_preserves_weights_exactly() {
+        // 2^53 + 1 can't be represented as an f64, so this only survives as an integer.
+        let path = write_temp_file("integer_weighted.csv", "1,2,9007199254740993\n2,3\n");
+        let adjacency_list = build_integer_weighted_adjacency_list_from_csv(&path).unwrap();
+        assert_eq!(adjacency_list[&1][&2], 9_007_199_254_740_993);
+        assert_eq!(adjacency_list[&2][&1], 9_007_199_254_740_993);
+        assert_eq!(adjacency_list[&3][&2], 1);
+
+        let path = write_temp_file("integer_weighted_bad.csv", "1,2,2.5\n");
+        assert!(build_integer_weighted_adjacency_list_from_csv(&path).unwrap_err().to_string().contains("2.5"));
+    }
+
+    #[test]
+    fn test_shortest_path_length_reachable_pair() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (1, 5), (5, 4)]);
+        assert_eq!(shortest_path_length(&adjacency_list, 1, 4), Some(2));
+        assert_eq!(shortest_path_length(&adjacency_list, 2, 5), Some(2));
+    }
+
+    #[test]
+    fn test_shortest_path_length_unreachable_pair() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (3, 4)]);
+        assert_eq!(shortest_path_length(&adjacency_list, 1, 4), None);
+    }
+
+    #[test]
+    fn test_shortest_path_length_to_self() {
+        let adjacency_list = build_sample_network();
+        assert_eq!(shortest_path_length(&adjacency_list, 2, 2), Some(0));
+    }
+
+    #[test]
+    fn test_shortest_path_reconstructs_unambiguous_route() {
+        // 1 - 2 - 3 - 4 with a dead-end branch 2 - 5.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (2, 5)]);
+        assert_eq!(shortest_path(&adjacency_list, 1, 4), Some(vec![1, 2, 3, 4]));
+        assert_eq!(shortest_path(&adjacency_list, 4, 5), Some(vec![4, 3, 2, 5]));
+    }
+
+    #[test]
+    fn test_shortest_path_trivial_and_unreachable() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (3, 4)]);
+        assert_eq!(shortest_path(&adjacency_list, 1, 1), Some(vec![1]));
+        assert_eq!(shortest_path(&adjacency_list, 1, 3), None);
+    }
+
+    #[test]
+    fn test_bfs_handles_node_missing_from_keys() {
+        // Node 4 only appears as a neighbor of 2, which used to panic on `adj_list[&4]`.
+        let mut adjacency_list = build_sample_network();
+        adjacency_list.entry(2).or_default().insert(4);
+        let distances = bfs(&adjacency_list, 1);
+        assert_eq!(distances[&4], 2);
+        assert_eq!(bfs(&adjacency_list, 4).len(), 1);
+    }
+
+    #[test]
+    fn test_bfs_from_absent_start_node() {
+        let adjacency_list = build_sample_network();
+        let distances = bfs(&adjacency_list, 42);
+        assert_eq!(distances.len(), 1);
+        assert_eq!(distances[&42], 0);
+    }
+
+    #[test]
+    fn test_metrics_on_empty_graph_return_empty_graph_error() {
+        let adjacency_list: HashMap<i32, HashSet<i32>> = HashMap::new();
+        assert_eq!(calculate_max_degree_of_separation(&adjacency_list), Err(GraphError::EmptyGraph));
+        assert_eq!(calculate_average_max_degree(&adjacency_list), Err(GraphError::EmptyGraph));
+        assert_eq!(calculate_average_shortest_path_length(&adjacency_list), Err(GraphError::EmptyGraph));
+        assert_eq!(calculate_mean_and_std_dev(&adjacency_list), Err(GraphError::EmptyGraph));
+        assert_eq!(calculate_normalized_separation_distribution(&adjacency_list), Err(GraphError::EmptyGraph));
+        assert_eq!(calculate_diameter(&adjacency_list), Err(GraphError::EmptyGraph));
+        assert_eq!(calculate_weighted_average_path_length(&HashMap::new()), Err(GraphError::EmptyGraph));
+        assert_eq!(compute_report(&adjacency_list), Err(GraphError::EmptyGraph));
+    }
+
+    #[test]
+    fn test_averages_on_single_self_looped_node_are_zero() {
+        let adjacency_list = build_network_from_edges(&[(1, 1)]);
+        assert_eq!(calculate_average_max_degree(&adjacency_list), Ok(0.0));
+        assert_eq!(calculate_average_shortest_path_length(&adjacency_list), Ok(0.0));
+        assert_eq!(calculate_mean_and_std_dev(&adjacency_list), Ok((0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_diameter_uses_largest_component() {
+        // A six-node star (diameter 2) and a separate four-node path (diameter 3).
+        let adjacency_list = build_network_from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5), (1, 6), (7, 8), (8, 9), (9, 10)]);
+        assert_eq!(calculate_diameter(&adjacency_list), Ok(2));
+        assert_eq!(calculate_component_diameters(&adjacency_list), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_diameter_of_empty_graph_is_an_error() {
+        let adjacency_list: HashMap<i32, HashSet<i32>> = HashMap::new();
+        assert_eq!(calculate_diameter(&adjacency_list), Err(GraphError::EmptyGraph));
+        assert!(calculate_component_diameters(&adjacency_list).is_empty());
+    }
+
+    #[test]
+    fn test_degree_distribution_on_sample_network() {
+        // 1 - 2 - 3: two nodes with one neighbor and one node with two.
+        let adjacency_list = build_sample_network();
+        let distribution = calculate_degree_distribution(&adjacency_list);
+        assert_eq!(distribution.len(), 2);
+        assert_eq!(distribution[&1], 2);
+        assert_eq!(distribution[&2], 1);
+        assert!((average_node_degree(&adjacency_list) - 4.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(max_node_degree(&adjacency_list), 2);
+    }
+
+    #[test]
+    fn test_clustering_coefficient_of_triangle_is_one() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(local_clustering_coefficient(&adjacency_list, 1), 1.0);
+        assert_eq!(global_clustering_coefficient(&adjacency_list), 1.0);
+    }
+
+    #[test]
+    fn test_clustering_coefficient_of_star_is_zero() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5)]);
+        assert_eq!(local_clustering_coefficient(&adjacency_list, 1), 0.0);
+        assert_eq!(local_clustering_coefficient(&adjacency_list, 2), 0.0);
+        assert_eq!(global_clustering_coefficient(&adjacency_list), 0.0);
+    }
+
+    #[test]
+    fn test_graph_built_programmatically() {
+        let mut graph = Graph::new();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(2, 3);
+        assert_eq!(graph.node_count(), 3);
+        let mut neighbors: Vec<i32> = graph.neighbors(2).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![1, 3]);
+        assert_eq!(graph.neighbors(42).count(), 0);
+        assert_eq!(graph.adjacency_list(), &build_sample_network());
+    }
+
+    #[test]
+    fn test_graph_from_csv_matches_adjacency_list_reader() {
+        let path = write_temp_file("graph.csv", "1,2\n2,3\n");
+        let graph = Graph::from_csv(&path).unwrap();
+        assert_eq!(graph.adjacency_list(), &build_sample_network());
+    }
+
+    #[test]
+    fn test_betweenness_centrality_peaks_at_path_center() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        let centrality = betweenness_centrality(&adjacency_list, false);
+        // Node 3 lies on the shortest paths 1-4, 1-5, 2-4 and 2-5.
+        assert!((centrality[&3] - 4.0).abs() < 1e-9);
+        assert!((centrality[&2] - 3.0).abs() < 1e-9);
+        assert_eq!(centrality[&1], 0.0);
+        let (&top_node, _) = centrality.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+        assert_eq!(top_node, 3);
+
+        // Normalized over the (n-1)(n-2)/2 = 6 pairs that don't involve the node.
+        let normalized = betweenness_centrality(&adjacency_list, true);
+        assert!((normalized[&3] - 4.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_betweenness_centrality_splits_between_equal_paths() {
+        // A square 1-2-4-3-1: the two shortest paths from 1 to 4 go through 2 and 3 equally.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 4), (4, 3), (3, 1)]);
+        let centrality = betweenness_centrality(&adjacency_list, false);
+        for node in 1..=4 {
+            assert!((centrality[&node] - 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_closeness_centrality_ranks_star_center_highest() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5)]);
+        let closeness = closeness_centrality(&adjacency_list);
+        assert!((closeness[&1] - 1.0).abs() < 1e-9);
+        for leaf in 2..=5 {
+            assert!(closeness[&leaf] < closeness[&1]);
+        }
+    }
+
+    #[test]
+    fn test_closeness_centrality_corrects_for_small_components() {
+        // A pair of nodes on their own would score 1.0 without the Wasserman-Faust correction.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5), (6, 7)]);
+        let closeness = closeness_centrality(&adjacency_list);
+        assert!(closeness[&6] < closeness[&1]);
+        assert!((closeness[&6] - 1.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pagerank_on_symmetric_graph_is_uniform() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1)]);
+        let ranks = pagerank(&adjacency_list, 0.85, 50);
+        let total: f64 = ranks.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        for rank in ranks.values() {
+            assert!((rank - 0.25).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_pagerank_redistributes_dangling_rank() {
+        let mut adjacency_list = build_sample_network();
+        adjacency_list.insert(4, HashSet::new());
+        let ranks = pagerank(&adjacency_list, 0.85, 50);
+        let total: f64 = ranks.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(ranks[&2] > ranks[&1]);
+    }
+
+    #[test]
+    fn test_largest_connected_component_keeps_only_bigger_component() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (5, 6)]);
+        let largest = largest_connected_component(&adjacency_list);
+        assert_eq!(largest, build_network_from_edges(&[(1, 2), (2, 3), (3, 4)]));
+        assert!(largest_connected_component(&HashMap::<i32, HashSet<i32>>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_connected_components_lists_groups_largest_first() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (3, 4), (4, 5), (6, 7), (7, 8), (8, 9)]);
+        let components = connected_components(&adjacency_list);
+        let sizes: Vec<usize> = components.iter().map(|component| component.len()).collect();
+        assert_eq!(sizes, vec![4, 3, 2]);
+        assert_eq!(components[0], [6, 7, 8, 9].into_iter().collect::<HashSet<i32>>());
+        assert_eq!(components[2], [1, 2].into_iter().collect::<HashSet<i32>>());
+    }
+
+    #[test]
+    fn test_directed_csv_does_not_create_reverse_edges() {
+        let path = write_temp_file("directed.csv", "1,2\n2,3\n");
+        let adjacency_list = build_directed_adjacency_list_from_csv(&path).unwrap();
+        assert!(adjacency_list[&1].contains(&2));
+        assert!(!adjacency_list[&2].contains(&1));
+        assert!(adjacency_list[&3].is_empty());
+        // Reachability is asymmetric: 3 is reachable from 1 but not the other way around.
+        assert_eq!(shortest_path_length(&adjacency_list, 1, 3), Some(2));
+        assert_eq!(shortest_path_length(&adjacency_list, 3, 1), None);
+    }
+
+    #[test]
+    fn test_csv_options_read_tsv() {
+        let path = write_temp_file("edges.tsv", "1\t2\n2\t3\n");
+        let options = CsvOptions { delimiter: b'\t', ..CsvOptions::default() };
+        let adjacency_list = build_adjacency_list_from_csv_with_options(&path, &options).unwrap();
+        assert_eq!(adjacency_list, build_sample_network());
+    }
+
+    #[test]
+    fn test_csv_options_skip_header_row() {
+        let path = write_temp_file("edges_header.csv", "source,target\n1,2\n2,3\n");
+        let options = CsvOptions { has_headers: true, ..CsvOptions::default() };
+        let adjacency_list = build_adjacency_list_from_csv_with_options(&path, &options).unwrap();
+        assert_eq!(adjacency_list, build_sample_network());
+    }
+
+    #[test]
+    fn test_csv_parse_error_reports_line_and_field() {
+        let path = write_temp_file("malformed.csv", "1,2\n2,3\n3,NaN\n");
+        let error = build_adjacency_list_from_csv(&path).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: failed to parse 'NaN' as node id");
+        let parse_error = error.downcast_ref::<CsvParseError>().unwrap();
+        assert_eq!(parse_error.line, 3);
+        assert_eq!(parse_error.field, "NaN");
+    }
+
+    #[test]
+    fn test_lenient_csv_skips_malformed_rows() {
+        let path = write_temp_file("dirty.csv", "1,2\nfoo,3\n2,3\n4\n");
+        let (adjacency_list, warnings) = build_adjacency_list_from_csv_lenient(&path).unwrap();
+        assert_eq!(adjacency_list, build_sample_network());
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("line 2") && warnings[0].contains("foo"));
+        assert!(warnings[1].contains("line 4"));
+    }
+
+    #[test]
+    fn test_precomputed_distances_match_direct_metrics() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (2, 5), (6, 7)]);
+        let all_distances = compute_all_distances(&adjacency_list);
+        assert_eq!(all_distances.len(), adjacency_list.len());
+        assert_eq!(all_distances[&1][&4], 3);
+
+        assert_eq!(calculate_max_degree_of_separation_from_distances(&all_distances), calculate_max_degree_of_separation(&adjacency_list));
+        // Floating point sums depend on hash map iteration order, so compare with a tolerance.
+        assert!((calculate_average_max_degree_from_distances(&all_distances).unwrap() - calculate_average_max_degree(&adjacency_list).unwrap()).abs() < 1e-9);
+        assert!((calculate_average_shortest_path_length_from_distances(&all_distances).unwrap() - calculate_average_shortest_path_length(&adjacency_list).unwrap()).abs() < 1e-9);
+        let (mean, std_dev) = calculate_mean_and_std_dev_from_distances(&all_distances).unwrap();
+        let (direct_mean, direct_std_dev) = calculate_mean_and_std_dev(&adjacency_list).unwrap();
+        assert!((mean - direct_mean).abs() < 1e-9 && (std_dev - direct_std_dev).abs() < 1e-9);
+        assert_eq!(calculate_normalized_separation_distribution_from_distances(&all_distances).unwrap().0, calculate_normalized_separation_distribution(&adjacency_list).unwrap().0);
+    }
+
+    #[test]
+    fn test_eccentricity_and_radius_on_path() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        assert_eq!(eccentricity(&adjacency_list, 1), Some(4));
+        assert_eq!(eccentricity(&adjacency_list, 5), Some(4));
+        assert_eq!(eccentricity(&adjacency_list, 3), Some(2));
+        assert!(eccentricity(&adjacency_list, 1) > eccentricity(&adjacency_list, 3));
+        assert_eq!(eccentricity(&adjacency_list, 42), None);
+        assert_eq!(radius(&adjacency_list), Some(2));
+    }
+
+    #[test]
+    fn test_radius_ignores_smaller_components() {
+        // The isolated pair would give a radius of 1 if it weren't excluded.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (6, 7)]);
+        assert_eq!(radius(&adjacency_list), Some(2));
+        assert_eq!(radius(&HashMap::<i32, HashSet<i32>>::new()), None);
+    }
+
+    #[test]
+    fn test_graph_center_of_odd_path_is_middle_node() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        assert_eq!(graph_center(&adjacency_list), vec![3]);
+    }
+
+    #[test]
+    fn test_graph_center_of_even_path_is_both_middle_nodes() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (7, 8)]);
+        assert_eq!(graph_center(&adjacency_list), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_average_path_length_does_not_overflow_i32() {
+        // Two paths of 1.5 billion hops sum to more than i32::MAX.
+        let long_distance = 1_500_000_000;
+        let mut all_distances: HashMap<i32, HashMap<i32, i32>> = HashMap::new();
+        all_distances.insert(1, [(1, 0), (2, long_distance)].into_iter().collect());
+        all_distances.insert(2, [(2, 0), (1, long_distance)].into_iter().collect());
+        assert_eq!(calculate_average_shortest_path_length_from_distances(&all_distances), Ok(long_distance as f64));
+        let (mean, std_dev) = calculate_mean_and_std_dev_from_distances(&all_distances).unwrap();
+        assert_eq!(mean, long_distance as f64);
+        assert_eq!(std_dev, 0.0);
+    }
+
+    #[test]
+    fn test_separation_distribution_of_degenerate_graphs_is_empty() {
+        let self_loops = build_network_from_edges(&[(1, 1), (2, 2)]);
+        let (distribution, degree, percentage) = calculate_normalized_separation_distribution(&self_loops).unwrap();
+        assert!(distribution.is_empty());
+        assert_eq!((degree, percentage), (0, 0.0));
+    }
+
+    #[test]
+    fn test_report_json_contains_expected_keys() {
+        let report = compute_report(&build_sample_network()).unwrap();
+        assert_eq!(report.max_degree_of_separation, 2);
+        assert_eq!(report.connected_components, 1);
+
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        for key in ["max_degree_of_separation", "average_max_degree", "connected_components", "average_shortest_path_length",
+                    "mean", "std_dev", "separation_distribution", "degree_with_max_percentage", "max_percentage"] {
+            assert!(json.contains(&format!("\"{}\":", key)), "missing key {}", key);
+        }
+        // Four of the six ordered pairs are one hop apart and two are two hops apart.
+        assert!(json.contains("\"1\": 0.6666666666666666"));
+        assert!(json.contains("\"2\": 0.3333333333333333"));
+    }
+
+    #[test]
+    fn test_write_distribution_csv_sorted_by_degree() {
+        let distribution: BTreeMap<i32, f64> = [(3, 0.25), (1, 0.5), (2, 0.25)].into_iter().collect();
+        let path = write_temp_file("distribution.csv", "");
+        write_distribution_csv(&distribution, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "degree,percentage\n1,0.5\n2,0.25\n3,0.25\n");
+    }
+
+    #[test]
+    fn test_separation_distribution_is_ordered_by_degree() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        let (distribution, _, _) = calculate_normalized_separation_distribution(&adjacency_list).unwrap();
+        let degrees: Vec<i32> = distribution.keys().copied().collect();
+        assert_eq!(degrees, vec![1, 2, 3, 4]);
+        assert_eq!(format!("{:?}", distribution), "{1: 0.4, 2: 0.3, 3: 0.2, 4: 0.1}");
+    }
+
+    #[test]
+    fn test_count_triangles() {
+        let triangle = build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(count_triangles(&triangle), 1);
+        let star = build_network_from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5)]);
+        assert_eq!(count_triangles(&star), 0);
+        // Two triangles sharing the edge 2-3.
+        let diamond = build_network_from_edges(&[(1, 2), (1, 3), (2, 3), (2, 4), (3, 4)]);
+        assert_eq!(count_triangles(&diamond), 2);
+    }
+
+    #[test]
+    fn test_k_core_removes_pendant_but_keeps_triangle() {
+        // A triangle with a two-node tail hanging off node 3.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5)]);
+        let core = k_core(&adjacency_list, 2);
+        assert_eq!(core, build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]));
+        assert!(k_core(&adjacency_list, 3).is_empty());
+        assert_eq!(k_core(&adjacency_list, 1), adjacency_list);
+    }
+
+    #[test]
+    fn test_articulation_points_of_bowtie() {
+        // Two triangles joined at node 3.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 3)]);
+        assert_eq!(articulation_points(&adjacency_list), [3].into_iter().collect::<HashSet<i32>>());
+    }
+
+    #[test]
+    fn test_articulation_points_across_components() {
+        // A path 1-2-3 (cut vertex 2) alongside a triangle with no cut vertices.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (4, 5), (5, 6), (6, 4)]);
+        assert_eq!(articulation_points(&adjacency_list), [2].into_iter().collect::<HashSet<i32>>());
+    }
+
+    #[test]
+    fn test_bridges_between_two_triangles() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 1), (4, 5), (5, 6), (6, 4), (3, 4)]);
+        assert_eq!(bridges(&adjacency_list), vec![(3, 4)]);
+    }
+
+    #[test]
+    fn test_bridges_of_path_are_every_edge() {
+        let adjacency_list = build_network_from_edges(&[(3, 2), (2, 1), (3, 4)]);
+        assert_eq!(bridges(&adjacency_list), vec![(1, 2), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn test_weighted_average_path_length() {
+        // Shortest distances: 1-2 = 1.0, 2-3 = 2.0, 1-3 = 3.0 (via 2), each counted in both directions.
+        let adjacency_list = build_weighted_sample_network();
+        assert!((calculate_weighted_average_path_length(&adjacency_list).unwrap() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weighted_average_path_length_excludes_unreachable_pairs() {
+        let mut adjacency_list = build_weighted_sample_network();
+        adjacency_list.entry(4).or_default().insert(5, 10.0);
+        adjacency_list.entry(5).or_default().insert(4, 10.0);
+        // The 1-2-3 component totals 12.0 over 6 pairs, and 4-5 adds 20.0 over 2 pairs.
+        assert!((calculate_weighted_average_path_length(&adjacency_list).unwrap() - 32.0 / 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_matrix_csv_builds_symmetric_adjacency_list() {
+        // Only the upper triangle lists the 0-1 edge; node 2 is connected to 1 in both directions.
+        let path = write_temp_file("matrix.csv", "0,1,0\n0,0,1\n0,1,0\n");
+        let adjacency_list = build_adjacency_list_from_matrix_csv(&path).unwrap();
+        assert_eq!(adjacency_list, build_network_from_edges(&[(0, 1), (1, 2)]));
+    }
+
+    #[test]
+    fn test_matrix_csv_rejects_non_square_matrix() {
+        let path = write_temp_file("matrix_bad.csv", "0,1,0\n1,0,1\n");
+        let error = build_adjacency_list_from_matrix_csv(&path).unwrap_err();
+        assert!(error.to_string().contains("not square"));
+    }
+
+    #[test]
+    fn test_matrix_csv_rejects_non_finite_entries() {
+        for (name, contents, field) in [("matrix_nan.csv", "0,1\n1,NaN\n", "NaN"), ("matrix_inf.csv", "0,inf\n1,0\n", "inf")] {
+            let path = write_temp_file(name, contents);
+            let error = build_adjacency_list_from_matrix_csv(&path).unwrap_err();
+            let error = error.downcast_ref::<CsvParseError>().expect("a CsvParseError");
+            assert_eq!(error.field, field);
+            assert_eq!(error.expected, "finite matrix entry");
+        }
+    }
+
+    #[test]
+    fn test_write_graphml_lists_nodes_and_edges_once() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4)]);
+        let path = write_temp_file("graph.graphml", "");
+        write_graphml(&adjacency_list, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("<?xml"));
+        assert_eq!(contents.matches("<node ").count(), 4);
+        assert_eq!(contents.matches("<edge ").count(), 4);
+        assert!(contents.contains(r#"<edge source="n3" target="n4"/>"#));
+        assert!(!contents.contains(r#"<edge source="n4" target="n3"/>"#));
+    }
+
+    #[test]
+    fn test_write_dot_writes_each_edge_once() {
+        let mut adjacency_list = build_sample_network();
+        adjacency_list.insert(9, HashSet::new());
+        let path = write_temp_file("graph.dot", "");
+
+        write_dot(&adjacency_list, &path, false).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "graph {\n    1 -- 2;\n    2 -- 3;\n}\n");
+
+        write_dot(&adjacency_list, &path, true).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "graph {\n    9;\n    1 -- 2;\n    2 -- 3;\n}\n");
+    }
+
+    #[test]
+    fn test_edge_count() {
+        assert_eq!(edge_count(&build_sample_network(), false), 2);
+        // The self-loop on node 3 counts as a single edge.
+        let with_self_loop = build_network_from_edges(&[(1, 2), (2, 3), (3, 3)]);
+        assert_eq!(edge_count(&with_self_loop, false), 3);
+
+        let mut directed: HashMap<i32, HashSet<i32>> = HashMap::new();
+        directed.entry(1).or_default().insert(2);
+        directed.entry(2).or_default().insert(1);
+        directed.entry(2).or_default().insert(3);
+        assert_eq!(edge_count(&directed, true), 3);
+    }
+
+    #[test]
+    fn test_node_count_and_density() {
+        let triangle = build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(node_count(&triangle), 3);
+        assert_eq!(density(&triangle), 1.0);
+
+        // A five-node path has 4 of the 10 possible edges.
+        let path = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        assert!((density(&path) - 0.4).abs() < 1e-9);
+        assert!(density(&path) < density(&triangle));
+
+        assert_eq!(density(&HashMap::new()), 0.0);
+    }
+
+    #[test]
+    fn test_csv_drops_self_loops_when_enabled() {
+        let path = write_temp_file("self_loops.csv", "1,2\n2,2\n2,3\n5,5\n");
+        let options = CsvOptions { drop_self_loops: true, ..CsvOptions::default() };
+        let (adjacency_list, dropped) = build_adjacency_list_from_csv_with_self_loop_count(&path, &options).unwrap();
+        assert_eq!(dropped, 2);
+        assert!(!adjacency_list[&2].contains(&2));
+        assert!(adjacency_list[&5].is_empty());
+
+        // By default the self-loops are kept.
+        let (adjacency_list, dropped) = build_adjacency_list_from_csv_with_self_loop_count(&path, &CsvOptions::default()).unwrap();
+        assert_eq!(dropped, 0);
+        assert!(adjacency_list[&2].contains(&2));
+    }
+
+    #[test]
+    fn test_build_adjacency_list_from_byte_slice() {
+        let data: &[u8] = b"source,target\n1,2\n2,3\n";
+        let adjacency_list = build_adjacency_list_from_reader(data, true).unwrap();
+        assert_eq!(adjacency_list, build_sample_network());
+
+        let without_header: &[u8] = b"1,2\n2,3\n";
+        assert_eq!(build_adjacency_list_from_reader(without_header, false).unwrap(), build_sample_network());
+    }
+
+    // Compress `contents` with gzip, as `gzip edges.csv` would.
+    fn gzip(contents: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(contents.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_gzip_csv_round_trip_from_byte_buffer() {
+        let compressed = gzip("1,2\n2,3\n");
+        let adjacency_list = build_adjacency_list_from_reader(GzDecoder::new(&compressed[..]), false).unwrap();
+        assert_eq!(adjacency_list, build_sample_network());
+
+        // A longer path, large enough for the encoder to use a dynamic Huffman block.
+        let edges: Vec<(i32, i32)> = (1..500).map(|node| (node, node + 1)).collect();
+        let csv: String = edges.iter().map(|(a, b)| format!("{},{}\n", a, b)).collect();
+        let compressed = gzip(&csv);
+        let adjacency_list = build_adjacency_list_from_reader(GzDecoder::new(&compressed[..]), false).unwrap();
+        assert_eq!(adjacency_list, build_network_from_edges(&edges));
+    }
+
+    #[test]
+    fn test_gzip_csv_file_is_detected_by_extension() {
+        let path = std::env::temp_dir().join(format!("final_project_{}_edges.csv.gz", std::process::id()));
+        std::fs::write(&path, gzip("1,2\n2,3\n")).unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(build_adjacency_list_from_csv(path).unwrap(), build_sample_network());
+        assert_eq!(build_adjacency_list_from_csv_gz(path).unwrap(), build_sample_network());
+    }
+
+    #[test]
+    fn test_gzip_rejects_corrupt_data() {
+        // Flip a byte of the CRC-32 in the trailer.
+        let mut corrupt = gzip("1,2\n2,3\n");
+        let crc_position = corrupt.len() - 8;
+        corrupt[crc_position] ^= 0xff;
+        assert!(build_adjacency_list_from_reader(GzDecoder::new(&corrupt[..]), false).is_err());
+    }
+
+    #[test]
+    fn test_is_bipartite() {
+        let even_cycle = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1)]);
+        assert!(is_bipartite(&even_cycle));
+        let triangle = build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert!(!is_bipartite(&triangle));
+        // The odd cycle is in a second component, so it's only found if coloring restarts there.
+        let mixed = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1), (5, 6), (6, 7), (7, 5)]);
+        assert!(!is_bipartite(&mixed));
+    }
+
+    #[test]
+    fn test_has_cycle() {
+        let tree = build_network_from_edges(&[(1, 2), (1, 3), (3, 4), (3, 5)]);
+        assert!(!has_cycle(&tree));
+        let triangle = build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert!(has_cycle(&triangle));
+        let forest_with_cycle = build_network_from_edges(&[(1, 2), (3, 4), (4, 5), (5, 6), (6, 3)]);
+        assert!(has_cycle(&forest_with_cycle));
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree_of_weighted_sample() {
+        // The direct 1-3 road (5.0) is dropped in favor of 1-2 (1.0) and 2-3 (2.0).
+        let adjacency_list = build_weighted_sample_network();
+        assert_eq!(minimum_spanning_tree(&adjacency_list), vec![(1, 2, 1.0), (2, 3, 2.0)]);
+    }
+
+    #[test]
+    fn test_minimum_spanning_forest_of_disconnected_graph() {
+        let mut adjacency_list = build_weighted_sample_network();
+        for &(a, b, weight) in &[(4, 5, 4.0), (5, 6, 1.5), (4, 6, 0.5)] {
+            adjacency_list.entry(a).or_default().insert(b, weight);
+            adjacency_list.entry(b).or_default().insert(a, weight);
+        }
+        let forest = minimum_spanning_tree(&adjacency_list);
+        assert_eq!(forest, vec![(4, 6, 0.5), (1, 2, 1.0), (5, 6, 1.5), (2, 3, 2.0)]);
+        let total: f64 = forest.iter().map(|&(_, _, weight)| weight).sum();
+        assert_eq!(total, 5.0);
+    }
+
+    #[test]
+    fn test_subgraph_keeps_only_internal_edges() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (2, 5)]);
+        let region: HashSet<i32> = [1, 2, 3].into_iter().collect();
+        assert_eq!(subgraph(&adjacency_list, &region), build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]));
+
+        // Nodes are kept even if none of their edges survive.
+        let scattered: HashSet<i32> = [1, 4].into_iter().collect();
+        let induced = subgraph(&adjacency_list, &scattered);
+        assert_eq!(induced.len(), 2);
+        assert!(induced.values().all(|neighbors| neighbors.is_empty()));
+    }
+
+    #[test]
+    fn test_remove_articulation_point_splits_graph() {
+        // Bowtie: two triangles joined at node 3.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 3)]);
+        let without_hub = remove_node(&adjacency_list, 3);
+        assert!(!without_hub.contains_key(&3));
+        assert!(without_hub.values().all(|neighbors| !neighbors.contains(&3)));
+        assert_eq!(calculate_connected_components(&adjacency_list), 1);
+        assert_eq!(calculate_connected_components(&without_hub), 2);
+        // The original graph is left untouched.
+        assert!(adjacency_list.contains_key(&3));
+    }
+
+    #[test]
+    fn test_path_length_percentiles_on_path() {
+        // Sorted pair distances of a five-node path: 1 x4, 2 x3, 3 x2, 4 x1.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        let percentiles = path_length_percentiles(&adjacency_list, &[50.0, 90.0, 100.0, 0.0]);
+        assert_eq!(percentiles, vec![(50.0, 2), (90.0, 3), (100.0, 4), (0.0, 1)]);
+        assert_eq!(path_length_percentiles(&HashMap::<i32, HashSet<i32>>::new(), &[50.0]), vec![(50.0, 0)]);
+    }
+
+    #[test]
+    fn test_fraction_within_degrees_on_sample_network() {
+        // Four of the six ordered pairs in 1 - 2 - 3 are one hop apart; the other two are two hops apart.
+        let adjacency_list = build_sample_network();
+        assert_eq!(fraction_within_degrees(&adjacency_list, 0), 0.0);
+        assert!((fraction_within_degrees(&adjacency_list, 1) - 4.0 / 6.0).abs() < 1e-9);
+        assert_eq!(fraction_within_degrees(&adjacency_list, 2), 1.0);
+        assert_eq!(fraction_within_degrees(&adjacency_list, 6), 1.0);
+    }
+
+    #[test]
+    fn test_metrics_work_with_string_node_ids() {
+        let mut adjacency_list: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for &(a, b) in &[("Paris", "Lyon"), ("Lyon", "Marseille"), ("Lyon", "Geneva"), ("Oslo", "Bergen")] {
+            adjacency_list.entry(a).or_default().insert(b);
+            adjacency_list.entry(b).or_default().insert(a);
+        }
+
+        assert_eq!(bfs(&adjacency_list, "Paris")["Marseille"], 2);
+        assert_eq!(shortest_path(&adjacency_list, "Paris", "Geneva"), Some(vec!["Paris", "Lyon", "Geneva"]));
+        assert_eq!(calculate_connected_components(&adjacency_list), 2);
+        assert_eq!(calculate_max_degree_of_separation(&adjacency_list), Ok(2));
+        assert_eq!(calculate_diameter(&adjacency_list), Ok(2));
+        assert_eq!(graph_center(&adjacency_list), vec!["Lyon"]);
+        assert!(calculate_average_shortest_path_length(&adjacency_list).unwrap() > 1.0);
+
+        let u64_list: HashMap<u64, HashSet<u64>> = build_sample_network().into_iter()
+            .map(|(node, neighbors)| (node as u64, neighbors.into_iter().map(|neighbor| neighbor as u64).collect()))
+            .collect();
+        assert_eq!(calculate_max_degree_of_separation(&u64_list), Ok(2));
+    }
+
+    #[test]
+    fn test_labeled_graph_from_csv_resolves_names() {
+        let path = write_temp_file("labeled.csv", "Paris,Lyon\nLyon,Marseille\n");
+        let labeled_graph = LabeledGraph::from_labeled_csv(&path).unwrap();
+        assert_eq!(labeled_graph.graph().node_count(), 3);
+
+        let paris = labeled_graph.id_for("Paris").unwrap();
+        let marseille = labeled_graph.id_for("Marseille").unwrap();
+        let route = shortest_path(labeled_graph.graph().adjacency_list(), paris, marseille).unwrap();
+        let names: Vec<&str> = route.iter().map(|&id| labeled_graph.label_for(id).unwrap()).collect();
+        assert_eq!(names, vec!["Paris", "Lyon", "Marseille"]);
+
+        assert_eq!(labeled_graph.id_for("Berlin"), None);
+        assert_eq!(labeled_graph.label_for(42), None);
+        assert_eq!(labeled_graph.label_for(-1), None);
+    }
+
+    #[test]
+    fn test_degree_assortativity() {
+        // Every edge of a star joins the hub to a leaf, so the degrees at the two ends are perfectly anti-correlated.
+        let star = build_network_from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4)]);
+        assert!((degree_assortativity(&star) + 1.0).abs() < 1e-9);
+
+        // In a cycle every degree is equal, so the correlation is undefined.
+        let cycle = build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(degree_assortativity(&cycle), 0.0);
+        assert_eq!(degree_assortativity(&HashMap::new()), 0.0);
+    }
+
+    #[test]
+    fn test_louvain_communities_splits_two_cliques() {
+        // Two 4-cliques joined by the single edge 4-5.
+        let mut edges = vec![(4, 5)];
+        for group in [[1, 2, 3, 4], [5, 6, 7, 8]] {
+            for (i, &a) in group.iter().enumerate() {
+                for &b in &group[i + 1..] {
+                    edges.push((a, b));
+                }
+            }
+        }
+        let communities = louvain_communities(&build_network_from_edges(&edges));
+
+        assert_eq!(communities.len(), 8);
+        for node in [1, 2, 3, 4] {
+            assert_eq!(communities[&node], 0);
+        }
+        for node in [5, 6, 7, 8] {
+            assert_eq!(communities[&node], 1);
+        }
+        assert!(louvain_communities(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_modularity_of_two_clique_partitions() {
+        let mut edges = vec![(4, 5)];
+        for group in [[1, 2, 3, 4], [5, 6, 7, 8]] {
+            for (i, &a) in group.iter().enumerate() {
+                for &b in &group[i + 1..] {
+                    edges.push((a, b));
+                }
+            }
+        }
+        let network = build_network_from_edges(&edges);
+
+        // 24 of the 26 edge ends stay inside a clique, and each clique holds half the degree: 24/26 - 0.5.
+        let by_clique: HashMap<i32, usize> = (1..=8).map(|node| (node, if node <= 4 { 0 } else { 1 })).collect();
+        assert!((modularity(&network, &by_clique) - (24.0 / 26.0 - 0.5)).abs() < 1e-9);
+        assert!((modularity(&network, &louvain_communities(&network)) - modularity(&network, &by_clique)).abs() < 1e-9);
+
+        // A partition that ignores the cliques scores far lower, and a single community always scores zero.
+        let scrambled: HashMap<i32, usize> = (1..=8).map(|node| (node, node as usize % 2)).collect();
+        assert!(modularity(&network, &scrambled).abs() < 0.25);
+        let together: HashMap<i32, usize> = (1..=8).map(|node| (node, 0)).collect();
+        assert!(modularity(&network, &together).abs() < 1e-9);
+        assert_eq!(modularity(&HashMap::new(), &HashMap::new()), 0.0);
+    }
+
+    #[test]
+    fn test_progress_callback_fires_once_per_node() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (5, 6)]);
+        let mut calls: Vec<(usize, usize)> = Vec::new();
+        let all_distances = compute_all_distances_with_progress(&adjacency_list, Some(&mut |completed, total| calls.push((completed, total))));
+
+        assert_eq!(calls.len(), adjacency_list.len());
+        assert_eq!(calls, (1..=6).map(|completed| (completed, 6)).collect::<Vec<_>>());
+        assert_eq!(all_distances, compute_all_distances(&adjacency_list));
+
+        let mut report_calls = 0;
+        let report = compute_report_with_progress(&adjacency_list, Some(&mut |_, _| report_calls += 1)).unwrap();
+        assert_eq!(report_calls, adjacency_list.len());
+        assert_eq!(report.separation_distribution, compute_report(&adjacency_list).unwrap().separation_distribution);
+    }
+
+    #[test]
+    fn test_harmonic_centrality() {
+        // On the path 1-2-3-4-5 the middle node is 1 hop from two nodes and 2 hops from the other two.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (6, 7)]);
+        let harmonic = harmonic_centrality(&adjacency_list);
+        assert!((harmonic[&3] - 3.0).abs() < 1e-9);
+        assert!((harmonic[&1] - (1.0 + 1.0 / 2.0 + 1.0 / 3.0 + 1.0 / 4.0)).abs() < 1e-9);
+        for node in [1, 2, 4, 5] {
+            assert!(harmonic[&3] > harmonic[&node]);
+        }
+        // The separate edge 6-7 only reaches one node; the rest of the graph adds nothing.
+        assert_eq!(harmonic[&6], 1.0);
+    }
+
+    #[test]
+    fn test_nodes_at_distance() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        assert_eq!(nodes_at_distance(&adjacency_list, 3, 0), HashSet::from_iter([3]));
+        assert_eq!(nodes_at_distance(&adjacency_list, 3, 1), HashSet::from_iter([2, 4]));
+        assert_eq!(nodes_at_distance(&adjacency_list, 3, 2), HashSet::from_iter([1, 5]));
+        assert!(nodes_at_distance(&adjacency_list, 3, 3).is_empty());
+    }
+
+    #[test]
+    fn test_bfs_limited_stops_at_max_depth() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (1, 6)]);
+        let distances = bfs_limited(&adjacency_list, 1, 2);
+        let expected: HashMap<i32, i32> = [(1, 0), (2, 1), (6, 1), (3, 2)].into_iter().collect();
+        assert_eq!(distances, expected);
+        assert!(!distances.contains_key(&4) && !distances.contains_key(&5));
+
+        assert_eq!(bfs_limited(&adjacency_list, 1, 0).len(), 1);
+        assert_eq!(bfs_limited(&adjacency_list, 1, 10), bfs(&adjacency_list, 1));
+    }
+
+    #[test]
+    fn test_bidirectional_bfs_matches_bfs_and_visits_fewer_nodes() {
+        let edges: Vec<(i32, i32)> = (0..999).map(|node| (node, node + 1)).collect();
+        let adjacency_list = build_network_from_edges(&edges);
+
+        assert_eq!(bidirectional_bfs(&adjacency_list, 400, 430), Some(bfs(&adjacency_list, 400)[&430]));
+        assert_eq!(bidirectional_bfs(&adjacency_list, 0, 999), Some(999));
+        assert_eq!(bidirectional_bfs(&adjacency_list, 7, 7), Some(0));
+        // Plain BFS from 400 visits all 1000 nodes; each of the two searches only spreads about 15 hops from its endpoint.
+        let visited = bidirectional_bfs_visited_count(&adjacency_list, 400, 430);
+        assert!(visited < 70, "visited {} nodes", visited);
+
+        let mut disconnected = adjacency_list.clone();
+        disconnected.insert(5000, HashSet::new());
+        assert_eq!(bidirectional_bfs(&disconnected, 0, 5000), None);
+    }
+
+    #[test]
+    fn test_graph_merge_unions_edges() {
+        // Two triangles sharing node 3, with the edge 2-3 present in both.
+        let mut graph = Graph::from(build_network_from_edges(&[(1, 2), (2, 3), (3, 1)]));
+        let mut other = build_network_from_edges(&[(3, 4), (4, 5), (5, 3), (2, 3)]);
+        other.insert(9, HashSet::new());
+        graph.merge(&other);
+
+        let expected = build_network_from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 3)]);
+        let mut merged = graph.adjacency_list().clone();
+        assert_eq!(merged.remove(&9), Some(HashSet::new()));
+        assert_eq!(merged, expected);
+        assert_eq!(graph.adjacency_list()[&3].len(), 4);
+    }
+
+    #[test]
+    fn test_shortest_path_deterministic_picks_the_same_path() {
+        // Four equally short routes from 1 to 6: via 2, 3, 4 or 5.
+        let edges = [(1, 5), (1, 4), (1, 3), (1, 2), (2, 6), (3, 6), (4, 6), (5, 6)];
+        let adjacency_list = build_network_from_edges(&edges);
+        let first = shortest_path_deterministic(&adjacency_list, 1, 6);
+        assert_eq!(first, Some(vec![1, 2, 6]));
+
+        // A separately built copy of the same graph iterates its sets in a different order, but the path doesn't change.
+        let mut reversed_edges = edges.to_vec();
+        reversed_edges.reverse();
+        assert_eq!(shortest_path_deterministic(&build_network_from_edges(&reversed_edges), 1, 6), first);
+        assert_eq!(shortest_path_deterministic(&adjacency_list, 1, 6), first);
+        assert_eq!(shortest_path_deterministic(&adjacency_list, 1, 99), None);
+    }
+
+    #[test]
+    fn test_all_eccentricities_match_individual_eccentricities() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (2, 5), (6, 7), (8, 8)]);
+        let eccentricities = all_eccentricities(&adjacency_list);
+        assert_eq!(eccentricities.len(), adjacency_list.len());
+        for (&node, &ecc) in &eccentricities {
+            assert_eq!(Some(ecc), eccentricity(&adjacency_list, node));
+        }
+        assert_eq!(eccentricities[&2], 2);
+        assert_eq!(eccentricities[&6], 1);
+        assert_eq!(eccentricities[&8], 0);
+    }
+
+    #[test]
+    fn test_node_strength() {
+        // Edges 1-2: 1.0, 2-3: 2.0 and 1-3: 5.0.
+        let adjacency_list = build_weighted_sample_network();
+        assert_eq!(node_strength(&adjacency_list, 1), 6.0);
+        assert_eq!(node_strength(&adjacency_list, 2), 3.0);
+        assert_eq!(node_strength(&adjacency_list, 3), 7.0);
+        assert_eq!(node_strength(&adjacency_list, 99), 0.0);
+
+        let expected: HashMap<i32, f64> = [(1, 6.0), (2, 3.0), (3, 7.0)].into_iter().collect();
+        assert_eq!(strength_distribution(&adjacency_list), expected);
+    }
+
+    #[test]
+    fn test_isolated_nodes() {
+        let mut adjacency_list = build_network_from_edges(&[(1, 2), (2, 3)]);
+        adjacency_list.insert(7, HashSet::new());
+        assert_eq!(isolated_nodes(&adjacency_list, &[]), vec![7]);
+
+        // Node 4 never appears in the edge list, so only the list of known ids reveals it.
+        assert_eq!(isolated_nodes(&adjacency_list, &[1, 2, 3, 4, 7]), vec![4, 7]);
+    }
+
+    #[test]
+    fn test_bfs_levels_group_nodes_by_distance() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (6, 7)]);
+        let levels = bfs_levels(&adjacency_list, 1);
+        assert_eq!(levels, vec![vec![1], vec![2, 3], vec![4], vec![5]]);
+
+        let distances = bfs(&adjacency_list, 1);
+        for (level, nodes) in levels.iter().enumerate() {
+            assert!(nodes.iter().all(|node| distances[node] == level as i32));
+        }
+        assert_eq!(bfs_levels(&adjacency_list, 99), vec![vec![99]]);
+    }
+
+    #[test]
+    fn test_read_edge_list_from_selected_columns() {
+        let path = write_temp_file("columns.csv", "7,1,2,1998,12.5\n8,2,3,2004,30.0\n");
+        let adjacency_list = build_adjacency_list_from_csv_cols(&path, 1, 2).unwrap();
+        assert_eq!(adjacency_list, build_network_from_edges(&[(1, 2), (2, 3)]));
+
+        // The first column holds row ids, not node ids, so reading columns 0 and 1 gives a different graph.
+        assert!(build_adjacency_list_from_csv_cols(&path, 0, 1).unwrap().contains_key(&7));
+        let err = build_adjacency_list_from_csv_cols(&path, 1, 4).unwrap_err();
+        assert_eq!(err.to_string(), "line 1: failed to parse '12.5' as node id");
+    }
+
+    #[test]
+    fn test_weighted_separation_distribution() {
+        // Shortest distances: 1-2 = 1.0, 2-3 = 2.0, 1-3 = 3.0 (via 2), each counted in both directions.
+        let adjacency_list = build_weighted_sample_network();
+        let distribution = weighted_separation_distribution(&adjacency_list, 2.0);
+        let expected: BTreeMap<i32, f64> = [(0, 2.0 / 6.0), (1, 4.0 / 6.0)].into_iter().collect();
+        assert_eq!(distribution, expected);
+        assert!((distribution.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        assert_eq!(weighted_separation_distribution(&adjacency_list, 1.0).keys().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(weighted_separation_distribution(&adjacency_list, 0.0).is_empty());
+        assert!(weighted_separation_distribution(&HashMap::new(), 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_is_connected() {
+        assert!(is_connected(&build_sample_network()));
+        assert!(!is_connected(&build_network_from_edges(&[(1, 2), (2, 3), (4, 5)])));
+        assert!(!is_connected(&HashMap::<i32, HashSet<i32>>::new()));
+    }
+
+    #[test]
+    fn test_compactify_round_trips_ids() {
+        let adjacency_list = build_network_from_edges(&[(1, 5000), (5000, 99999), (1, 99999), (42, 42)]);
+        let (compact, ids) = compactify(&adjacency_list);
+        assert_eq!(ids, vec![1, 42, 5000, 99999]);
+        assert_eq!(compact, vec![vec![2, 3], vec![1], vec![0, 3], vec![0, 2]]);
+
+        for (index, neighbors) in compact.iter().enumerate() {
+            let original: HashSet<i32> = neighbors.iter().map(|&neighbor| ids[neighbor]).collect();
+            assert_eq!(original, adjacency_list[&ids[index]]);
+        }
+        assert_eq!(compactify(&HashMap::new()), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut cycle: HashMap<i32, HashSet<i32>> = HashMap::new();
+        for (from, to) in [(1, 2), (2, 3), (3, 4), (4, 1)] {
+            cycle.entry(from).or_default().insert(to);
+        }
+        assert_eq!(strongly_connected_components(&cycle), vec![vec![1, 2, 3, 4]]);
+
+        // In a one-way chain nothing can get back, so every node is its own component.
+        let mut chain: HashMap<i32, HashSet<i32>> = HashMap::new();
+        for node in 1..5 {
+            chain.entry(node).or_default().insert(node + 1);
+        }
+        chain.insert(5, HashSet::new());
+        assert_eq!(strongly_connected_components(&chain), vec![vec![1], vec![2], vec![3], vec![4], vec![5]]);
+
+        // Two cycles joined one way stay separate components.
+        let mut joined = cycle.clone();
+        for (from, to) in [(4, 5), (5, 6), (6, 5)] {
+            joined.entry(from).or_default().insert(to);
+        }
+        assert_eq!(strongly_connected_components(&joined), vec![vec![1, 2, 3, 4], vec![5, 6]]);
+    }
+
+    #[test]
+    fn test_approximate_diameter() {
+        // On a path the double sweep always ends up running from one end to the other.
+        let edges: Vec<(i32, i32)> = (1..10).map(|node| (node, node + 1)).collect();
+        let path = build_network_from_edges(&edges);
+        assert_eq!(approximate_diameter(&path), 9);
+        assert_eq!(Ok(approximate_diameter(&path)), calculate_diameter(&path));
+
+        // Like `calculate_diameter`, only the largest component is measured.
+        let mut with_triangle = path.clone();
+        with_triangle.extend(build_network_from_edges(&[(20, 21), (21, 22), (22, 20)]));
+        assert_eq!(approximate_diameter(&with_triangle), 9);
+        assert_eq!(approximate_diameter(&HashMap::<i32, HashSet<i32>>::new()), 0);
+    }
+
+    #[test]
+    fn test_erdos_renyi_is_deterministic_per_seed() {
+        let graph = generate_erdos_renyi(50, 0.1, 42);
+        assert_eq!(graph, generate_erdos_renyi(50, 0.1, 42));
+        assert_ne!(graph, generate_erdos_renyi(50, 0.1, 43));
+        assert_eq!(graph.len(), 50);
+
+        // About 10% of the 1225 possible edges should be present.
+        let edges = edge_count(&graph, false);
+        assert!((60..=190).contains(&edges), "{} edges", edges);
+
+        assert_eq!(edge_count(&generate_erdos_renyi(10, 0.0, 1), false), 0);
+        assert_eq!(edge_count(&generate_erdos_renyi(10, 1.0, 1), false), 45);
+    }
+
+    #[test]
+    fn test_grid_generator() {
+        let grid = generate_grid(3, 3);
+        assert_eq!(grid.len(), 9);
+        assert_eq!(edge_count(&grid, false), 12);
+        assert_eq!(grid[&4], HashSet::from_iter([1, 3, 5, 7]));
+        assert_eq!(calculate_diameter(&grid), Ok(4));
+        assert_eq!(calculate_diameter(&generate_grid(4, 6)), Ok(8));
+        // The 36 pairs of cells are 12 at distance 1, 14 at 2, 8 at 3 and 2 at 4, averaging exactly 2.
+        assert!((calculate_average_shortest_path_length(&grid).unwrap() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compute_report_timed_records_every_metric() {
+        let adjacency_list = build_sample_network();
+        let (report, timings) = compute_report_timed(&adjacency_list).unwrap();
+        assert_eq!(report.max_degree_of_separation, compute_report(&adjacency_list).unwrap().max_degree_of_separation);
+
+        let mut metrics: Vec<&str> = timings.keys().copied().collect();
+        metrics.sort_unstable();
+        assert_eq!(metrics, vec!["all_distances", "average_max_degree", "average_shortest_path_length", "connected_components",
+                                 "max_degree_of_separation", "mean_and_std_dev", "separation_distribution"]);
+        assert_eq!(compute_report_timed(&HashMap::new()), Err(GraphError::EmptyGraph));
+    }
+
+    #[test]
+    fn test_jaccard_similarity() {
+        // 1 and 2 both neighbor 3 and 4; 1 also neighbors 5 and 2 also neighbors 6.
+        let adjacency_list = build_network_from_edges(&[(1, 3), (1, 4), (1, 5), (2, 3), (2, 4), (2, 6)]);
+        assert!((jaccard_similarity(&adjacency_list, 1, 2) - 2.0 / 4.0).abs() < 1e-9);
+        assert_eq!(jaccard_similarity(&adjacency_list, 3, 4), 1.0);
+        assert_eq!(jaccard_similarity(&adjacency_list, 5, 6), 0.0);
+        assert_eq!(jaccard_similarity(&adjacency_list, 98, 99), 0.0);
+    }
+
+    #[test]
+    fn test_common_neighbors() {
+        let adjacency_list = build_network_from_edges(&[(1, 3), (1, 4), (1, 5), (2, 3), (2, 4), (2, 6), (1, 2)]);
+        assert_eq!(common_neighbors(&adjacency_list, 1, 2), HashSet::from_iter([3, 4]));
+        assert_eq!(common_neighbors(&adjacency_list, 2, 1), common_neighbors(&adjacency_list, 1, 2));
+        assert!(common_neighbors(&adjacency_list, 5, 6).is_empty());
+        assert!(common_neighbors(&adjacency_list, 1, 99).is_empty());
+    }
+
+    #[test]
+    fn test_reachable_counts_per_component() {
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (5, 6), (7, 7)]);
+        let counts = reachable_counts(&adjacency_list);
+        let expected: HashMap<i32, usize> = [(1, 3), (2, 3), (3, 3), (4, 3), (5, 1), (6, 1), (7, 0)].into_iter().collect();
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn test_read_nodes_and_edges_keeps_isolated_nodes() {
+        let path = write_temp_file("nodes_and_edges.csv", "1\n2\n3\n9\n1,2\n2,3\n4,5\n");
+        let adjacency_list = build_adjacency_list_from_nodes_and_edges_csv(&path).unwrap();
+
+        let mut expected = build_network_from_edges(&[(1, 2), (2, 3), (4, 5)]);
+        expected.insert(9, HashSet::new());
+        assert_eq!(adjacency_list, expected);
+        assert_eq!(isolated_nodes(&adjacency_list, &[]), vec![9]);
+
+        let path = write_temp_file("nodes_and_edges_bad.csv", "1\nx\n");
+        let err = build_adjacency_list_from_nodes_and_edges_csv(&path).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: failed to parse 'x' as node id");
+    }
+
+    #[test]
+    fn test_weighted_betweenness_centrality_follows_distances() {
+        // A ring 1-2-3-4-5-1 whose closing edge 5-1 is too long to be on any shortest route,
+        // so weighted routes run along the path 1-2-3-4-5 and node 3 is the bottleneck.
+        let mut adjacency_list: HashMap<i32, HashMap<i32, f64>> = HashMap::new();
+        for (a, b, weight) in [(1, 2, 1.0), (2, 3, 1.0), (3, 4, 1.0), (4, 5, 1.0), (5, 1, 10.0)] {
+            adjacency_list.entry(a).or_default().insert(b, weight);
+            adjacency_list.entry(b).or_default().insert(a, weight);
+        }
+        let weighted = weighted_betweenness_centrality(&adjacency_list);
+        let expected: HashMap<i32, f64> = [(1, 0.0), (2, 3.0), (3, 4.0), (4, 3.0), (5, 0.0)].into_iter().collect();
+        for (node, score) in &expected {
+            assert!((weighted[node] - score).abs() < 1e-9, "node {}: {}", node, weighted[node]);
+        }
+
+        // Ignoring the weights, the ring is symmetric and every node scores the same.
+        let unweighted = betweenness_centrality(&build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (5, 1)]), false);
+        assert!(unweighted.values().all(|&score| (score - 1.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_weighted_betweenness_splits_equal_routes() {
+        // Two equally long routes from 1 to 4, via 2 and via 3, each carry half of that pair's dependency.
+        let mut adjacency_list: HashMap<i32, HashMap<i32, f64>> = HashMap::new();
+        for (a, b, weight) in [(1, 2, 1.0), (2, 4, 2.0), (1, 3, 2.0), (3, 4, 1.0)] {
+            adjacency_list.entry(a).or_default().insert(b, weight);
+            adjacency_list.entry(b).or_default().insert(a, weight);
+        }
+        let weighted = weighted_betweenness_centrality(&adjacency_list);
+        assert!((weighted[&2] - weighted[&3]).abs() < 1e-9);
+        assert!(weighted[&2] > 0.0);
+    }
+
+    #[test]
+    fn test_weighted_betweenness_with_zero_weight_edge() {
+        // The path 1-2-3-4 where 2 and 3 are joined by a zero-length edge, so they are equally far from 1 and 4.
+        // Every route still runs along the path, giving the same scores as the unweighted path.
+        let mut adjacency_list: HashMap<i32, HashMap<i32, f64>> = HashMap::new();
+        for (a, b, weight) in [(1, 2, 1.0), (2, 3, 0.0), (3, 4, 1.0)] {
+            adjacency_list.entry(a).or_default().insert(b, weight);
+            adjacency_list.entry(b).or_default().insert(a, weight);
+        }
+        let weighted = weighted_betweenness_centrality(&adjacency_list);
+        let expected: HashMap<i32, f64> = [(1, 0.0), (2, 2.0), (3, 2.0), (4, 0.0)].into_iter().collect();
+        for (node, score) in &expected {
+            assert!((weighted[node] - score).abs() < 1e-9, "node {}: {}", node, weighted[node]);
+        }
+    }
+
+    #[test]
+    fn test_degree_centrality_ranks_star_center_first() {
+        let star = build_network_from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4)]);
+        assert_eq!(degree_centrality(&star), vec![(0, 1.0), (1, 0.25), (2, 0.25), (3, 0.25), (4, 0.25)]);
+
+        let single: HashMap<i32, HashSet<i32>> = [(7, HashSet::new())].into_iter().collect();
+        assert_eq!(degree_centrality(&single), vec![(7, 0.0)]);
+        assert!(degree_centrality(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_connectivity_tracker_counts_merging_components() {
+        let mut tracker = ConnectivityTracker::new();
+        assert_eq!(tracker.num_components(), 0);
+
+        for node in 1..=6 {
+            tracker.add_node(node);
+        }
+        assert_eq!(tracker.num_components(), 6);
+
+        assert!(tracker.add_edge(1, 2));
+        assert!(tracker.add_edge(3, 4));
+        assert_eq!(tracker.num_components(), 4);
+        assert!(tracker.add_edge(2, 3));
+        assert_eq!(tracker.num_components(), 3);
+
+        // An edge inside a component changes nothing; an edge to a new node extends a component.
+        assert!(!tracker.add_edge(1, 4));
+        assert_eq!(tracker.num_components(), 3);
+        assert!(tracker.add_edge(6, 7));
+        assert_eq!(tracker.num_components(), 3);
+
+        assert!(tracker.connected(1, 4));
+        assert!(!tracker.connected(1, 5));
+        assert!(!tracker.connected(1, 99));
+    }
+
+    #[test]
+    fn test_diameter_path_returns_farthest_endpoints() {
+        let mut adjacency_list = build_network_from_edges(&[(3, 1), (1, 4), (4, 5), (5, 2)]);
+        adjacency_list.extend(build_network_from_edges(&[(10, 11)]));
+        assert_eq!(diameter_path(&adjacency_list), Some((2, 3, 4)));
+
+        // In a 4-cycle two pairs are 2 apart; the smaller one is chosen.
+        assert_eq!(diameter_path(&build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1)])), Some((1, 3, 2)));
+        assert_eq!(diameter_path(&HashMap::<i32, HashSet<i32>>::new()), None);
+    }
+
+    #[test]
+    fn test_read_adjacency_list_from_json() {
+        let path = write_temp_file("adjacency.json", "{\"1\": [2, 3],\n \"2\": [1],\n \"4\": []}\n");
+        let adjacency_list = build_adjacency_list_from_json(&path).unwrap();
+
+        // 3 only appears as a neighbor of 1, so its back-edge is added.
+        let mut expected = build_network_from_edges(&[(1, 2), (1, 3)]);
+        expected.insert(4, HashSet::new());
+        assert_eq!(adjacency_list, expected);
+
+        assert!(build_adjacency_list_from_json(&write_temp_file("empty.json", "{}")).unwrap().is_empty());
+        assert!(build_adjacency_list_from_json(&write_temp_file("bad.json", "{\"1\": [2, x]}")).is_err());
+    }
+
+    #[test]
+    fn test_json_adjacency_list_rejects_whitespace_in_keys() {
+        for (name, contents) in [("key_trailing_space.json", "{\"1 \": [2]}"), ("key_leading_space.json", "{\" 2\": [1]}")] {
+            let err = build_adjacency_list_from_json(&write_temp_file(name, contents)).unwrap_err();
+            assert!(err.to_string().starts_with("invalid node id"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_friendship_paradox_ratio() {
+        // A hub with 10 leaves: average degree 20/11, but following an edge lands on the hub half the time,
+        // so the average neighbor degree is (100 + 10) / 20 = 5.5.
+        let edges: Vec<(i32, i32)> = (1..=10).map(|leaf| (0, leaf)).collect();
+        let ratio = friendship_paradox_ratio(&build_network_from_edges(&edges));
+        assert!((ratio - 5.5 / (20.0 / 11.0)).abs() < 1e-9);
+        assert!(ratio > 3.0);
+
+        // When every node has the same degree there is no paradox.
+        assert!((friendship_paradox_ratio(&build_network_from_edges(&[(1, 2), (2, 3), (3, 1)])) - 1.0).abs() < 1e-9);
+        assert_eq!(friendship_paradox_ratio(&HashMap::new()), 0.0);
+    }
+
+    #[test]
+    fn test_component_average_path_lengths() {
+        // A 5-node path (average 2.0 over 20 ordered pairs) and a single edge (average 1.0 over 2 pairs).
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (6, 7), (8, 8)]);
+        let (averages, overall) = calculate_component_average_path_lengths(&adjacency_list).unwrap();
+
+        let expected: HashMap<usize, f64> = [(0, 2.0), (1, 1.0), (2, 0.0)].into_iter().collect();
+        assert_eq!(averages, expected);
+        assert!((overall - (2.0 * 20.0 + 1.0 * 2.0) / 22.0).abs() < 1e-9);
+        assert!((overall - calculate_average_shortest_path_length(&adjacency_list).unwrap()).abs() < 1e-9);
+        assert_eq!(calculate_component_average_path_lengths(&HashMap::<i32, HashSet<i32>>::new()), Err(GraphError::EmptyGraph));
+    }
+
+    #[test]
+    fn test_write_node_metrics_csv() {
+        let path = write_temp_file("node_metrics.csv", "");
+        write_node_metrics_csv(&build_sample_network(), &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+
+        assert_eq!(lines[0], "node,degree,eccentricity,closeness,betweenness,clustering");
+        assert_eq!(lines.len(), 1 + build_sample_network().len());
+        // On the path 1-2-3 the middle node is one hop from both ends and lies on the 1-3 route.
+        assert_eq!(lines[2], "2,2,1,1,1,0");
+    }
+
+    #[test]
+    fn test_maximum_bipartite_matching() {
+        // Towns 1-3 and depots 10-12. Depot 10 is the only option for town 2.
+        let adjacency_list = build_network_from_edges(&[(1, 10), (1, 11), (2, 10), (3, 10), (3, 12)]);
+        let left: HashSet<i32> = HashSet::from_iter([1, 2, 3]);
+        let right: HashSet<i32> = HashSet::from_iter([10, 11, 12]);
+        let matching = maximum_bipartite_matching(&adjacency_list, &left, &right).unwrap();
+        // Town 1 first takes depot 10, then gives it up so every town is served.
+        assert_eq!(matching, vec![(1, 11), (2, 10), (3, 12)]);
+
+        let limited = build_network_from_edges(&[(1, 10), (2, 10), (3, 10), (1, 11)]);
+        assert_eq!(maximum_bipartite_matching(&limited, &left, &right).unwrap().len(), 2);
+
+        let mut invalid = adjacency_list.clone();
+        invalid.get_mut(&1).unwrap().insert(2);
+        invalid.get_mut(&2).unwrap().insert(1);
+        assert_eq!(maximum_bipartite_matching(&invalid, &left, &right), Err(GraphError::EdgeWithinSide(1, 2)));
+    }
+
+    #[test]
+    fn test_maximum_bipartite_matching_rejects_node_on_both_sides() {
+        // With 10 on both sides, it could be matched to itself through the self-loop.
+        let adjacency_list = build_network_from_edges(&[(1, 10), (10, 10), (2, 11)]);
+        let left: HashSet<i32> = HashSet::from_iter([1, 2, 10]);
+        let right: HashSet<i32> = HashSet::from_iter([10, 11]);
+        assert_eq!(maximum_bipartite_matching(&adjacency_list, &left, &right), Err(GraphError::NodeOnBothSides(10)));
+
+        // Edges between undeclared nodes are ignored.
+        let with_undeclared = build_network_from_edges(&[(1, 10), (2, 11), (20, 21)]);
+        let left: HashSet<i32> = HashSet::from_iter([1, 2]);
+        assert_eq!(maximum_bipartite_matching(&with_undeclared, &left, &right), Ok(vec![(1, 10), (2, 11)]));
+    }
+
+    #[test]
+    fn test_trimmed_mean_separation_ignores_long_tail() {
+        // A triangle 1-2-3 with a tail 3-4-5-6-7-8: the tail adds a few very long paths.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (1, 3), (3, 4), (4, 5), (5, 6), (6, 7), (7, 8)]);
+        let (mean, _) = calculate_mean_and_std_dev(&adjacency_list).unwrap();
+
+        assert!((trimmed_mean_separation(&adjacency_list, 0.0) - mean).abs() < 1e-9);
+        let trimmed = trimmed_mean_separation(&adjacency_list, 0.2);
+        assert!(trimmed < mean, "trimmed mean {} should be below the plain mean {}", trimmed, mean);
+
+        // Trimming as much as possible still keeps the middle distances; the path 1-2-3 has distances 1, 1, 1, 1, 2, 2.
+        assert!((trimmed_mean_separation(&build_sample_network(), 0.5) - 1.0).abs() < 1e-9);
+        assert_eq!(trimmed_mean_separation(&HashMap::<i32, HashSet<i32>>::new(), 0.1), 0.0);
+    }
+
+    #[test]
+    fn test_bfs_with_horizon_marks_distant_nodes() {
+        // 7-8 is a separate component, so it is beyond any horizon.
+        let adjacency_list = build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (1, 6), (7, 8)]);
+        let distances = bfs_with_horizon(&adjacency_list, 1, 2);
+        let expected: HashMap<i32, i32> = [(1, 0), (2, 1), (6, 1), (3, 2), (4, -1), (5, -1), (7, -1), (8, -1)].into_iter().collect();
+        assert_eq!(distances, expected);
+
+        // With a horizon past the diameter, only the other component gets the sentinel.
+        let distances = bfs_with_horizon(&adjacency_list, 1, 10);
+        assert_eq!(distances[&5], 4);
+        assert_eq!(distances[&7], -1);
+    }
+
+    #[test]
+    fn test_edges_lists_each_edge_once() {
+        let triangle = build_network_from_edges(&[(3, 1), (2, 3), (1, 2)]);
+        assert_eq!(edges(&triangle), vec![(1, 2), (1, 3), (2, 3)]);
+        assert!(edges(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_shared_sample_fixtures() {
+        let disconnected = build_disconnected_sample();
+        assert_eq!(calculate_connected_components(&disconnected), 2);
+        assert!(!is_connected(&disconnected));
+        assert_eq!(calculate_diameter(&disconnected), Ok(2));
+
+        let cycle = build_cyclic_sample();
+        assert!(is_connected(&cycle) && has_cycle(&cycle));
+        assert_eq!(radius(&cycle), Some(2));
+        assert_eq!(graph_center(&cycle), vec![1, 2, 3, 4, 5]);
+
+        let star = build_star_sample(5);
+        assert_eq!(star.len(), 6);
+        assert_eq!(graph_center(&star), vec![0]);
+        assert_eq!(calculate_max_degree_of_separation(&star), Ok(2));
+        assert_eq!(build_star_sample(0).len(), 1);
+    }
+
+    #[test]
+    fn test_wiener_index() {
+        // A path of n nodes has Wiener index (n^3 - n) / 6.
+        for n in [1, 2, 5, 10] {
+            let edges: Vec<(i32, i32)> = (1..n).map(|node| (node, node + 1)).collect();
+            let mut path = build_network_from_edges(&edges);
+            path.entry(1).or_default();
+            assert_eq!(wiener_index(&path), ((n * n * n - n) / 6) as i64);
+        }
+
+        // The two components of 1-2-3 and 4-5 contribute 1 + 1 + 2 and 1; cross-component pairs are skipped.
+        assert_eq!(wiener_index(&build_disconnected_sample()), 5);
+        assert_eq!(wiener_index(&HashMap::<i32, HashSet<i32>>::new()), 0);
+    }
+
+    #[test]
+    fn test_neighbors_of_absent_node_is_empty() {
+        let adjacency_list = build_sample_network();
+        assert_eq!(neighbors(&adjacency_list, 2), &HashSet::from_iter([1, 3]));
+        assert!(neighbors(&adjacency_list, 42).is_empty());
+    }
+
+    #[test]
+    fn test_separation_distribution_picks_smallest_tied_degree() {
+        // In a star with 3 leaves, the 6 ordered hub-leaf pairs are 1 hop apart and the 6 leaf-leaf pairs 2 hops.
+        let star = build_star_sample(3);
+        let (distribution, degree_with_max_percentage, max_percentage) = calculate_normalized_separation_distribution(&star).unwrap();
+        assert_eq!(distribution[&1], distribution[&2]);
+        assert_eq!(degree_with_max_percentage, 1);
+        assert!((max_percentage - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_write_dot_with_attribute() {
+        let path = write_temp_file("graph_attribute.dot", "");
+        write_dot_with_attribute(&build_sample_network(), NodeAttribute::Betweenness, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        // Only the middle node of the path 1-2-3 lies between two others.
+        assert_eq!(
+            contents,
+            "graph {\n    node [shape=circle];\n    1 [betweenness=0, width=0.30];\n    2 [betweenness=1, width=1.30];\n    \
+             3 [betweenness=0, width=0.30];\n    1 -- 2;\n    2 -- 3;\n}\n"
+        );
+
+        write_dot_with_attribute(&build_sample_network(), NodeAttribute::Eccentricity, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("1 [eccentricity=2, width=1.30];"));
+        assert!(contents.contains("2 [eccentricity=1, width=0.80];"));
+    }
+
+    #[test]
+    fn test_assert_symmetric_reports_missing_back_edge() {
+        // The sample network stores both directions of each edge.
+        assert_eq!(assert_symmetric(&build_sample_network()), Ok(()));
+
+        let mut adjacency_list = build_sample_network();
+        adjacency_list.get_mut(&3).unwrap().remove(&2);
+        adjacency_list.entry(1).or_default().insert(4);
+        assert_eq!(assert_symmetric(&adjacency_list), Err((1, 4)));
+        adjacency_list.get_mut(&1).unwrap().remove(&4);
+        assert_eq!(assert_symmetric(&adjacency_list), Err((2, 3)));
+    }
+}
@@ -1,58 +1,59 @@
-mod data_reading;
-mod separation_deg;
+use final_project::{data_reading, report};
 use std::error::Error;
 
+const DEFAULT_FILE_PATH: &str = "euroroad.csv";
+
 fn main() -> Result<(), Box<dyn Error>> {
-    let file_path = "euroroad.csv";
-    // Build an adjacency list representation of the road network
-    let adjacency_list = data_reading::build_adjacency_list_from_csv(file_path)?;
+    // Usage: final_project [--drop-self-loops] [edge_list.csv] [report.json]
+    let (flags, positional): (Vec<String>, Vec<String>) = std::env::args().skip(1).partition(|arg| arg.starts_with("--"));
+    if let Some(flag) = flags.iter().find(|flag| *flag != "--drop-self-loops") {
+        return Err(format!("unknown option {}", flag).into());
+    }
+    let drop_self_loops = !flags.is_empty();
+    let mut args = positional.into_iter();
+    let file_path = args.next().unwrap_or_else(|| {
+        eprintln!("Usage: final_project [--drop-self-loops] [edge_list.csv] [report.json] (defaulting to {})", DEFAULT_FILE_PATH);
+        DEFAULT_FILE_PATH.to_string()
+    });
+    let json_output_path = args.next();
+
+    // Build an adjacency list representation of the road network, dropping self-loop rows only if asked to.
+    let options = data_reading::CsvOptions { drop_self_loops, ..data_reading::CsvOptions::default() };
+    let (adjacency_list, dropped_self_loops) = data_reading::build_adjacency_list_from_csv_with_self_loop_count(&file_path, &options)?;
+    if dropped_self_loops > 0 {
+        eprintln!("Warning: dropped {} self-loop rows from {}", dropped_self_loops, file_path);
+    }
 
-    // Calculate the maximum degree of separation in the road network graph.
-    let max_degree_of_separation = separation_deg::calculate_max_degree_of_separation(&adjacency_list);
-    println!("Max Degree of Separation: {}", max_degree_of_separation);
+    // Calculate every separation metric, running the BFS from each city only once.
+    let report = report::compute_report(&adjacency_list)?;
 
-    // Calculate the average maximum degree of the road network graph.
-    let average_max_degree = separation_deg::calculate_average_max_degree(&adjacency_list);
-    println!("Average Max Degree: {}", average_max_degree);
+    // The maximum degree of separation in the road network graph.
+    println!("Max Degree of Separation: {}", report.max_degree_of_separation);
 
-    // Calculate the number of connected components in the road network graph.
-    let connected_components = separation_deg::calculate_connected_components(&adjacency_list);
-    println!("Number of Connected Components: {}", connected_components);
+    // The average maximum degree of the road network graph.
+    println!("Average Max Degree: {}", report.average_max_degree);
 
-    // Calculate the average shortest path length in the road network graph.
-    let average_shortest_path_length = separation_deg::calculate_average_shortest_path_length(&adjacency_list);
-    println!("Average Shortest Path Length: {}", average_shortest_path_length);
+    // The number of connected components in the road network graph.
+    println!("Number of Connected Components: {}", report.connected_components);
 
-    // Calculate the mean and standard deviation of separation degrees in the graph.
-    let (mean, std_dev) = separation_deg::calculate_mean_and_std_dev(&adjacency_list);
+    // The average shortest path length in the road network graph.
+    println!("Average Shortest Path Length: {}", report.average_shortest_path_length);
 
-    // Print the results
-    println!("Mean of Separations: {}", mean);
-    println!("Standard Deviation of Separations: {}", std_dev);
+    // The mean and standard deviation of separation degrees in the graph.
+    println!("Mean of Separations: {}", report.mean);
+    println!("Standard Deviation of Separations: {}", report.std_dev);
 
-    // Calculate the normalized separation distribution and find the degree with the maximum percentage.
-    let (normalized_separation_distribution, degree_with_max_percentage, max_percentage) = separation_deg::calculate_normalized_separation_distribution(&adjacency_list);
+    // The normalized separation distribution and the degree with the maximum percentage.
     println!("----------------");
-    println!("Separation Distribution (degree: percentage): {:?}", normalized_separation_distribution);
+    println!("Separation Distribution (degree: percentage): {:?}", report.separation_distribution);
     println!("----------------");
-    println!("Degree with Maximum Percentage: {}, Percentage: {}", degree_with_max_percentage, max_percentage);
+    println!("Degree with Maximum Percentage: {}, Percentage: {}", report.degree_with_max_percentage, report.max_percentage);
 
-    Ok(())
-}
-
-// Unit test
-#[cfg(test)]
-mod tests {
-    use super::separation_deg::calculate_normalized_separation_distribution;
-    use super::separation_deg::build_sample_network;
-
-    #[test]
-    fn test_separation_distribution_sums_to_one() {
-        let adjacency_list = build_sample_network();
-        // Calculate the normalized separation distribution which shows how nodes are separated in the network.
-        let (separation_distribution, _, _) = calculate_normalized_separation_distribution(&adjacency_list);
-        let sum_of_percentages: f64 = separation_distribution.values().sum();
-        assert!((sum_of_percentages - 1.0).abs() < f64::EPSILON);
+    // Optionally save the report as JSON.
+    if let Some(json_output_path) = json_output_path {
+        std::fs::write(&json_output_path, serde_json::to_string_pretty(&report)?)?;
+        println!("Report written to {}", json_output_path);
     }
-}
 
+    Ok(())
+}
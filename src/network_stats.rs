@@ -0,0 +1,210 @@
+use hashbrown::{HashMap, HashSet};
+
+// The degree of a node is the number of its direct neighbors.
+// This maps each degree `k` to the number of nodes that have exactly `k` neighbors.
+pub fn calculate_degree_distribution(adj_list: &HashMap<i32, HashSet<i32>>) -> HashMap<usize, usize> {
+    let mut distribution: HashMap<usize, usize> = HashMap::new();
+
+    for neighbors in adj_list.values() {
+        *distribution.entry(neighbors.len()).or_insert(0) += 1;
+    }
+
+    distribution
+}
+
+// The average number of direct neighbors per node. Returns 0.0 for an empty graph.
+pub fn average_node_degree(adj_list: &HashMap<i32, HashSet<i32>>) -> f64 {
+    if adj_list.is_empty() {
+        return 0.0;
+    }
+
+    let total_degree: usize = adj_list.values().map(|neighbors| neighbors.len()).sum();
+    total_degree as f64 / adj_list.len() as f64
+}
+
+// The largest number of direct neighbors of any node. Returns 0 for an empty graph.
+pub fn max_node_degree(adj_list: &HashMap<i32, HashSet<i32>>) -> usize {
+    adj_list.values().map(|neighbors| neighbors.len()).max().unwrap_or(0)
+}
+
+// The strength of a node is the weighted counterpart of its degree: the sum of the weights of its edges.
+// A city with a few long roads and one with many short roads can have the same strength but very different degrees.
+// A node that isn't in the graph has strength 0.0.
+pub fn node_strength(adj_list: &HashMap<i32, HashMap<i32, f64>>, node: i32) -> f64 {
+    adj_list.get(&node).map_or(0.0, |neighbors| neighbors.values().sum())
+}
+
+// The strength of every node in a weighted graph.
+pub fn strength_distribution(adj_list: &HashMap<i32, HashMap<i32, f64>>) -> HashMap<i32, f64> {
+    adj_list.keys().map(|&node| (node, node_strength(adj_list, node))).collect()
+}
+
+// The fraction of pairs of a node's neighbors that are themselves directly connected.
+// Nodes with fewer than two neighbors have no such pairs and are given a coefficient of 0.0.
+pub fn local_clustering_coefficient(adj_list: &HashMap<i32, HashSet<i32>>, node: i32) -> f64 {
+    let neighbors: Vec<i32> = match adj_list.get(&node) {
+        Some(neighbors) => neighbors.iter().copied().filter(|&neighbor| neighbor != node).collect(),
+        None => return 0.0,
+    };
+    let degree = neighbors.len();
+    if degree < 2 {
+        return 0.0;
+    }
+
+    // Count each connected pair of neighbors once.
+    let mut links = 0;
+    for (i, a) in neighbors.iter().enumerate() {
+        for b in &neighbors[i + 1..] {
+            if adj_list.get(a).is_some_and(|a_neighbors| a_neighbors.contains(b)) {
+                links += 1;
+            }
+        }
+    }
+
+    let possible_links = degree * (degree - 1) / 2;
+    links as f64 / possible_links as f64
+}
+
+// The average local clustering coefficient over all nodes with at least two neighbors.
+// Nodes with degree 0 or 1 are excluded from the average. Returns 0.0 if no node qualifies.
+pub fn global_clustering_coefficient(adj_list: &HashMap<i32, HashSet<i32>>) -> f64 {
+    let coefficients: Vec<f64> = adj_list.iter()
+        .filter(|(&node, neighbors)| neighbors.iter().filter(|&&neighbor| neighbor != node).count() >= 2)
+        .map(|(&node, _)| local_clustering_coefficient(adj_list, node))
+        .collect();
+
+    if coefficients.is_empty() {
+        return 0.0;
+    }
+
+    coefficients.iter().sum::<f64>() / coefficients.len() as f64
+}
+
+// Count the triangles (three mutually connected nodes) in an undirected graph.
+// Each edge contributes the number of common neighbors of its endpoints, and every triangle is found once from each of its three edges.
+pub fn count_triangles(adj_list: &HashMap<i32, HashSet<i32>>) -> usize {
+    let mut closed_edges = 0;
+
+    for (&a, a_neighbors) in adj_list {
+        for &b in a_neighbors {
+            // Visit each undirected edge once.
+            if b <= a {
+                continue;
+            }
+            if let Some(b_neighbors) = adj_list.get(&b) {
+                closed_edges += a_neighbors.intersection(b_neighbors)
+                    .filter(|&&c| c != a && c != b)
+                    .count();
+            }
+        }
+    }
+
+    closed_edges / 3
+}
+
+// The number of edges in the graph. In an undirected graph every edge appears in both endpoints' neighbor sets,
+// so those are halved; a self-loop only appears once and is counted once. In a directed graph each entry is one edge.
+pub fn edge_count(adj_list: &HashMap<i32, HashSet<i32>>, directed: bool) -> usize {
+    let entries: usize = adj_list.values().map(|neighbors| neighbors.len()).sum();
+    if directed {
+        return entries;
+    }
+
+    let self_loops = adj_list.iter().filter(|(node, neighbors)| neighbors.contains(*node)).count();
+    (entries - self_loops) / 2 + self_loops
+}
+
+// The nodes without any edges, in ascending order. An edge-list CSV only mentions nodes that have edges, so
+// `known_nodes` can list every node id in the dataset; any of those missing from the adjacency list is isolated too.
+pub fn isolated_nodes(adj_list: &HashMap<i32, HashSet<i32>>, known_nodes: &[i32]) -> Vec<i32> {
+    let mut isolated: Vec<i32> = adj_list.iter()
+        .filter(|(_, neighbors)| neighbors.is_empty())
+        .map(|(&node, _)| node)
+        .chain(known_nodes.iter().copied().filter(|node| !adj_list.contains_key(node)))
+        .collect();
+    isolated.sort_unstable();
+    isolated.dedup();
+    isolated
+}
+
+// The number of nodes in the graph.
+pub fn node_count(adj_list: &HashMap<i32, HashSet<i32>>) -> usize {
+    adj_list.len()
+}
+
+// The fraction of all possible undirected edges that are present, 2E / (V(V-1)).
+// Graphs with fewer than two nodes have no possible edges, so their density is 0.0.
+pub fn density(adj_list: &HashMap<i32, HashSet<i32>>) -> f64 {
+    let nodes = node_count(adj_list);
+    if nodes < 2 {
+        return 0.0;
+    }
+
+    2.0 * edge_count(adj_list, false) as f64 / (nodes * (nodes - 1)) as f64
+}
+
+// The Pearson correlation between the degrees at the two ends of each edge, counting every undirected edge in both directions.
+// Near +1 hubs link to hubs, near -1 hubs link to low-degree nodes. Returns 0.0 when it is undefined:
+// a graph with no edges, or one where every edge joins nodes of equal degree (e.g. a cycle).
+pub fn degree_assortativity(adj_list: &HashMap<i32, HashSet<i32>>) -> f64 {
+    let degree = |node: &i32| adj_list.get(node).map_or(0, |neighbors| neighbors.len()) as f64;
+
+    let mut ends: Vec<(f64, f64)> = Vec::new();
+    for (node, neighbors) in adj_list {
+        for neighbor in neighbors.iter().filter(|&neighbor| neighbor != node) {
+            ends.push((degree(node), degree(neighbor)));
+        }
+    }
+    if ends.is_empty() {
+        return 0.0;
+    }
+
+    let count = ends.len() as f64;
+    let mean_x = ends.iter().map(|&(x, _)| x).sum::<f64>() / count;
+    let mean_y = ends.iter().map(|&(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = ends.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance_x: f64 = ends.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    let variance_y: f64 = ends.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return 0.0;
+    }
+    covariance / (variance_x * variance_y).sqrt()
+}
+
+// The nodes adjacent to both `a` and `b`. A node that isn't in the graph has no neighbors to share.
+pub fn common_neighbors(adj_list: &HashMap<i32, HashSet<i32>>, a: i32, b: i32) -> HashSet<i32> {
+    match (adj_list.get(&a), adj_list.get(&b)) {
+        (Some(a_neighbors), Some(b_neighbors)) => a_neighbors.intersection(b_neighbors).copied().collect(),
+        _ => HashSet::new(),
+    }
+}
+
+// How structurally similar two nodes are: the share of their combined neighbors that they have in common,
+// |N(a) ∩ N(b)| / |N(a) ∪ N(b)|. Returns 0.0 if neither node has any neighbors.
+pub fn jaccard_similarity(adj_list: &HashMap<i32, HashSet<i32>>, a: i32, b: i32) -> f64 {
+    let empty = HashSet::new();
+    let a_neighbors = adj_list.get(&a).unwrap_or(&empty);
+    let b_neighbors = adj_list.get(&b).unwrap_or(&empty);
+
+    let union = a_neighbors.union(b_neighbors).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a_neighbors.intersection(b_neighbors).count() as f64 / union as f64
+}
+
+// The friendship paradox: on average, your friends have more friends than you do. This is the average degree of a
+// node reached by following a random edge, sum(k^2) / sum(k), divided by the average node degree. High-degree nodes
+// are at the end of many edges, so the ratio is at least 1 and grows with the spread of degrees.
+// Returns 0.0 for a graph without edges.
+pub fn friendship_paradox_ratio(adj_list: &HashMap<i32, HashSet<i32>>) -> f64 {
+    let total_degree: usize = adj_list.values().map(|neighbors| neighbors.len()).sum();
+    if total_degree == 0 {
+        return 0.0;
+    }
+
+    let total_squared_degree: usize = adj_list.values().map(|neighbors| neighbors.len() * neighbors.len()).sum();
+    let average_neighbor_degree = total_squared_degree as f64 / total_degree as f64;
+    average_neighbor_degree / average_node_degree(adj_list)
+}
@@ -0,0 +1,78 @@
+use crate::separation_deg::{self, GraphError};
+use hashbrown::{HashMap, HashSet};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+// All of the separation metrics for a road network, collected so they can be saved for a dashboard,
+// e.g. with `serde_json::to_string_pretty`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsReport {
+    pub max_degree_of_separation: i32,
+    pub average_max_degree: f64,
+    pub connected_components: usize,
+    pub average_shortest_path_length: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub separation_distribution: BTreeMap<i32, f64>,
+    pub degree_with_max_percentage: i32,
+    pub max_percentage: f64,
+}
+
+// Compute every metric in the report, running the BFS from each node only once.
+// Fails with `GraphError::EmptyGraph` if the graph has no nodes.
+pub fn compute_report(adjacency_list: &HashMap<i32, HashSet<i32>>) -> Result<MetricsReport, GraphError> {
+    compute_report_with_progress(adjacency_list, None)
+}
+
+// Like `compute_report`, reporting `(completed, total)` BFS passes to `progress` as the distances are computed.
+pub fn compute_report_with_progress(
+    adjacency_list: &HashMap<i32, HashSet<i32>>,
+    progress: Option<&mut dyn FnMut(usize, usize)>,
+) -> Result<MetricsReport, GraphError> {
+    build_report(adjacency_list, progress, &mut HashMap::new())
+}
+
+// Like `compute_report`, but also returns how long each step took, keyed by the name of the metric it produced
+// ("all_distances" is the shared BFS pass). Useful for deciding what to cache or parallelize.
+pub fn compute_report_timed(adjacency_list: &HashMap<i32, HashSet<i32>>) -> Result<(MetricsReport, HashMap<&'static str, Duration>), GraphError> {
+    let mut timings = HashMap::new();
+    let report = build_report(adjacency_list, None, &mut timings)?;
+    Ok((report, timings))
+}
+
+fn build_report(
+    adjacency_list: &HashMap<i32, HashSet<i32>>,
+    progress: Option<&mut dyn FnMut(usize, usize)>,
+    timings: &mut HashMap<&'static str, Duration>,
+) -> Result<MetricsReport, GraphError> {
+    let all_distances = timed(timings, "all_distances", || separation_deg::compute_all_distances_with_progress(adjacency_list, progress));
+    let (mean, std_dev) = timed(timings, "mean_and_std_dev", || separation_deg::calculate_mean_and_std_dev_from_distances(&all_distances))?;
+    let (separation_distribution, degree_with_max_percentage, max_percentage) = timed(timings, "separation_distribution", || {
+        separation_deg::calculate_normalized_separation_distribution_from_distances(&all_distances)
+    })?;
+
+    Ok(MetricsReport {
+        max_degree_of_separation: timed(timings, "max_degree_of_separation", || {
+            separation_deg::calculate_max_degree_of_separation_from_distances(&all_distances)
+        })?,
+        average_max_degree: timed(timings, "average_max_degree", || separation_deg::calculate_average_max_degree_from_distances(&all_distances))?,
+        connected_components: timed(timings, "connected_components", || separation_deg::calculate_connected_components(adjacency_list)),
+        average_shortest_path_length: timed(timings, "average_shortest_path_length", || {
+            separation_deg::calculate_average_shortest_path_length_from_distances(&all_distances)
+        })?,
+        mean,
+        std_dev,
+        separation_distribution,
+        degree_with_max_percentage,
+        max_percentage,
+    })
+}
+
+// Run one step of the report, recording how long it took under `metric`.
+fn timed<T>(timings: &mut HashMap<&'static str, Duration>, metric: &'static str, compute: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = compute();
+    timings.insert(metric, start.elapsed());
+    result
+}
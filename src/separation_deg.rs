@@ -1,9 +1,36 @@
 use hashbrown::{HashMap, HashSet};
-use std::collections::VecDeque;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+// Why a metric couldn't be computed. The summary metrics have no meaningful value for a graph without nodes,
+// and bipartite algorithms need every edge to cross between the two declared sides.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphError {
+    EmptyGraph,
+    EdgeWithinSide(i32, i32),
+    NodeOnBothSides(i32),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::EmptyGraph => write!(f, "the graph has no nodes"),
+            GraphError::EdgeWithinSide(a, b) => write!(f, "edge {}-{} does not go between the two sides", a, b),
+            GraphError::NodeOnBothSides(node) => write!(f, "node {} is declared on both sides", node),
+        }
+    }
+}
+
+impl Error for GraphError {}
 
 // Perform a Breadth-First Search (BFS) to find the shortest paths from a starting node to all other nodes.
 // Returns a HashMap where the keys are node identifiers and the values are the shortest distances from the start node.
-pub fn bfs(adj_list: &HashMap<i32, HashSet<i32>>, start_node: i32) -> HashMap<i32, i32> {
+// Nodes without an entry in the adjacency list (including an absent start node) are treated as having no neighbors.
+// Like the separation metrics below, it works with any hashable `Copy` node id, such as `i32`, `u64` or `&str` city names.
+pub fn bfs<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, start_node: N) -> HashMap<N, i32> {
     let mut visited = HashSet::new();
     let mut queue = VecDeque::new();
     let mut distances = HashMap::new();
@@ -17,9 +44,11 @@ pub fn bfs(adj_list: &HashMap<i32, HashSet<i32>>, start_node: i32) -> HashMap<i3
         distances.insert(current_node, distance);
 
         // Look at all the neighbors of the current node.
-        for &neighbor in &adj_list[&current_node] {
-            if visited.insert(neighbor) {
-                queue.push_back((neighbor, distance + 1));
+        if let Some(neighbors) = adj_list.get(&current_node) {
+            for &neighbor in neighbors {
+                if visited.insert(neighbor) {
+                    queue.push_back((neighbor, distance + 1));
+                }
             }
         }
     }
@@ -27,68 +56,622 @@ pub fn bfs(adj_list: &HashMap<i32, HashSet<i32>>, start_node: i32) -> HashMap<i3
     distances
 }
 
-// This is the maximum shortest path length from any node to any other node.
-pub fn calculate_max_degree_of_separation(adjacency_list: &HashMap<i32, HashSet<i32>>) -> i32 {
-    let max_degrees = adjacency_list.keys()
-        .map(|&city| {
-            let distances = bfs(&adjacency_list, city);
-            *distances.values().max().unwrap_or(&0)
+// Find the shortest path length between two specific nodes.
+// The BFS stops as soon as `to` is dequeued, so nearby targets don't require exploring the whole graph.
+// Returns `None` if `to` is unreachable from `from`.
+pub fn shortest_path_length<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, from: N, to: N) -> Option<i32> {
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    visited.insert(from);
+    queue.push_back((from, 0));
+
+    while let Some((current_node, distance)) = queue.pop_front() {
+        if current_node == to {
+            return Some(distance);
+        }
+
+        if let Some(neighbors) = adj_list.get(&current_node) {
+            for &neighbor in neighbors {
+                if visited.insert(neighbor) {
+                    queue.push_back((neighbor, distance + 1));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Reconstruct one shortest path between two nodes as the sequence of nodes a traveler passes through.
+// Each node's BFS predecessor is recorded so the path can be walked back from `to` once it is reached.
+// Returns `None` if `to` is unreachable from `from`.
+pub fn shortest_path<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, from: N, to: N) -> Option<Vec<N>> {
+    shortest_path_visiting(adj_list, from, to, |neighbors| neighbors.iter().copied().collect())
+}
+
+// Like `shortest_path`, but neighbors are explored in ascending id order, so when several shortest paths exist
+// the same one is returned on every run instead of whichever the `HashSet` iteration order happens to find first.
+pub fn shortest_path_deterministic<N: Eq + Hash + Copy + Ord>(adj_list: &HashMap<N, HashSet<N>>, from: N, to: N) -> Option<Vec<N>> {
+    shortest_path_visiting(adj_list, from, to, |neighbors| {
+        let mut sorted: Vec<N> = neighbors.iter().copied().collect();
+        sorted.sort_unstable();
+        sorted
+    })
+}
+
+// The BFS behind both path functions; `order` lists a node's neighbors in the order they should be explored.
+fn shortest_path_visiting<N: Eq + Hash + Copy>(
+    adj_list: &HashMap<N, HashSet<N>>,
+    from: N,
+    to: N,
+    order: impl Fn(&HashSet<N>) -> Vec<N>,
+) -> Option<Vec<N>> {
+    let mut predecessors: HashMap<N, N> = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    visited.insert(from);
+    queue.push_back(from);
+
+    while let Some(current_node) = queue.pop_front() {
+        if current_node == to {
+            // Walk the predecessor chain back to the start, then reverse it.
+            let mut path = vec![to];
+            let mut node = to;
+            while let Some(&previous) = predecessors.get(&node) {
+                path.push(previous);
+                node = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        if let Some(neighbors) = adj_list.get(&current_node) {
+            for neighbor in order(neighbors) {
+                if visited.insert(neighbor) {
+                    predecessors.insert(neighbor, current_node);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// A BFS that stops expanding once it reaches `max_depth`, for "cities within 3 hops" style queries.
+// Nodes at exactly `max_depth` are included; anything further away is never visited and is absent from the result.
+pub fn bfs_limited<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, start: N, max_depth: i32) -> HashMap<N, i32> {
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    let mut distances = HashMap::new();
+
+    visited.insert(start);
+    queue.push_back((start, 0));
+
+    while let Some((current_node, distance)) = queue.pop_front() {
+        distances.insert(current_node, distance);
+        if distance >= max_depth {
+            continue;
+        }
+
+        if let Some(neighbors) = adj_list.get(&current_node) {
+            for &neighbor in neighbors {
+                if visited.insert(neighbor) {
+                    queue.push_back((neighbor, distance + 1));
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+// Like `bfs_limited`, but every other node of the graph is also listed, with the sentinel distance -1 meaning
+// "not reachable within `horizon` hops". The traversal still stops at the horizon, so "local separation" can be
+// measured on a huge graph without exploring it all, while the result still covers every node.
+pub fn bfs_with_horizon<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, start: N, horizon: i32) -> HashMap<N, i32> {
+    let mut distances = bfs_limited(adj_list, start, horizon);
+    for &node in adj_list.keys() {
+        distances.entry(node).or_insert(-1);
+    }
+    distances
+}
+
+// The nodes grouped by BFS level, to show how separation expands ring by ring: level 0 is just `start`,
+// level 1 its neighbors, and so on. Each level is sorted so the output reads the same on every run.
+pub fn bfs_levels<N: Eq + Hash + Copy + Ord>(adj_list: &HashMap<N, HashSet<N>>, start: N) -> Vec<Vec<N>> {
+    let mut visited = HashSet::new();
+    visited.insert(start);
+    let mut levels = vec![vec![start]];
+
+    loop {
+        let mut next_level: Vec<N> = levels[levels.len() - 1].iter()
+            .filter_map(|node| adj_list.get(node))
+            .flatten()
+            .copied()
+            .filter(|&neighbor| visited.insert(neighbor))
+            .collect();
+        if next_level.is_empty() {
+            return levels;
+        }
+        next_level.sort_unstable();
+        levels.push(next_level);
+    }
+}
+
+// The ring of nodes whose shortest distance from `start` is exactly `k`. Distance 0 is just `start` itself.
+pub fn nodes_at_distance<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, start: N, k: i32) -> HashSet<N> {
+    bfs_limited(adj_list, start, k)
+        .into_iter()
+        .filter(|&(_, distance)| distance == k)
+        .map(|(node, _)| node)
+        .collect()
+}
+
+// The shortest distance between two nodes, found by growing BFS frontiers from both ends until they meet.
+// Each search only has to reach about half the distance, so far fewer nodes are visited than with a one-sided BFS.
+// Assumes an undirected graph, since the search from `to` follows edges backwards. Returns `None` if `to` is unreachable.
+pub fn bidirectional_bfs<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, from: N, to: N) -> Option<i32> {
+    bidirectional_search(adj_list, from, to).0
+}
+
+// The number of nodes `bidirectional_bfs` visits, so tests can check how much of the graph it explored.
+#[cfg(test)]
+pub fn bidirectional_bfs_visited_count<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, from: N, to: N) -> usize {
+    bidirectional_search(adj_list, from, to).1
+}
+
+// Returns the distance (if any) and the number of nodes visited by both searches together.
+fn bidirectional_search<N: Eq + Hash + Copy>(adj_list: &HashMap<N, HashSet<N>>, from: N, to: N) -> (Option<i32>, usize) {
+    if from == to {
+        return (Some(0), 1);
+    }
+
+    let mut forward: HashMap<N, i32> = HashMap::new();
+    let mut backward: HashMap<N, i32> = HashMap::new();
+    forward.insert(from, 0);
+    backward.insert(to, 0);
+    let mut forward_frontier = vec![from];
+    let mut backward_frontier = vec![to];
+
+    while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        // Expand one whole level of the smaller frontier, keeping the two searches balanced.
+        let (frontier, visited, other) = if forward_frontier.len() <= backward_frontier.len() {
+            (&mut forward_frontier, &mut forward, &backward)
+        } else {
+            (&mut backward_frontier, &mut backward, &forward)
+        };
+
+        // Several meeting points can appear in the same level, so finish the level and keep the shortest.
+        let mut shortest: Option<i32> = None;
+        let mut next_frontier = Vec::new();
+        for node in frontier.drain(..) {
+            let distance = visited[&node];
+            if let Some(neighbors) = adj_list.get(&node) {
+                for &neighbor in neighbors {
+                    if let Some(&other_distance) = other.get(&neighbor) {
+                        let total = distance + 1 + other_distance;
+                        shortest = Some(shortest.map_or(total, |shortest| shortest.min(total)));
+                    }
+                    if !visited.contains_key(&neighbor) {
+                        visited.insert(neighbor, distance + 1);
+                        next_frontier.push(neighbor);
+                    }
+                }
+            }
+        }
+        *frontier = next_frontier;
+
+        if shortest.is_some() {
+            return (shortest, forward.len() + backward.len());
+        }
+    }
+
+    (None, forward.len() + backward.len())
+}
+
+// An entry in the Dijkstra priority queue. The ordering is reversed so that `BinaryHeap`, which is a max-heap,
+// pops the node with the smallest tentative distance first.
+#[derive(Debug, PartialEq)]
+pub(crate) struct HeapEntry {
+    pub(crate) distance: f64,
+    pub(crate) node: i32,
+}
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance).then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Run Dijkstra's algorithm to find the weighted shortest distances from a starting node to all reachable nodes.
+// The adjacency list maps each node to its neighbors and the weight of the connecting edge.
+// Negative (or NaN) weights are not valid for Dijkstra, so those edges are skipped.
+pub fn dijkstra(adj_list: &HashMap<i32, HashMap<i32, f64>>, start: i32) -> HashMap<i32, f64> {
+    let mut distances: HashMap<i32, f64> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    distances.insert(start, 0.0);
+    heap.push(HeapEntry { distance: 0.0, node: start });
+
+    while let Some(HeapEntry { distance, node }) = heap.pop() {
+        // Skip stale entries left in the heap after a shorter distance was found.
+        if distance > distances[&node] {
+            continue;
+        }
+
+        let Some(neighbors) = adj_list.get(&node) else {
+            continue;
+        };
+        for (&neighbor, &weight) in neighbors {
+            if weight.is_nan() || weight < 0.0 {
+                continue;
+            }
+            let candidate = distance + weight;
+            if distances.get(&neighbor).is_none_or(|&current| candidate < current) {
+                distances.insert(neighbor, candidate);
+                heap.push(HeapEntry { distance: candidate, node: neighbor });
+            }
+        }
+    }
+
+    distances
+}
+
+// Run a BFS from every node once and keep the resulting distance maps, keyed by source node.
+// The separation metrics below can all be computed from this, so a full report only pays for the BFS passes once.
+pub fn compute_all_distances<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> HashMap<N, HashMap<N, i32>> {
+    compute_all_distances_with_progress(adjacency_list, None)
+}
+
+// Like `compute_all_distances`, but calls `progress(completed, total)` after each source's BFS so a long run can show progress.
+pub fn compute_all_distances_with_progress<N: Eq + Hash + Copy>(
+    adjacency_list: &HashMap<N, HashSet<N>>,
+    mut progress: Option<&mut dyn FnMut(usize, usize)>,
+) -> HashMap<N, HashMap<N, i32>> {
+    let total = adjacency_list.len();
+    adjacency_list.keys()
+        .enumerate()
+        .map(|(completed, &city)| {
+            let distances = bfs(adjacency_list, city);
+            if let Some(progress) = progress.as_mut() {
+                progress(completed + 1, total);
+            }
+            (city, distances)
         })
+        .collect()
+}
+
+// This is the maximum shortest path length from any node to any other node.
+// Like the other summary metrics, it returns `GraphError::EmptyGraph` for a graph without nodes.
+pub fn calculate_max_degree_of_separation<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Result<i32, GraphError> {
+    calculate_max_degree_of_separation_from_distances(&compute_all_distances(adjacency_list))
+}
+
+// `calculate_max_degree_of_separation` from a precomputed `compute_all_distances` map, with the same result and no extra BFS passes.
+pub fn calculate_max_degree_of_separation_from_distances<N>(all_distances: &HashMap<N, HashMap<N, i32>>) -> Result<i32, GraphError> {
+    if all_distances.is_empty() {
+        return Err(GraphError::EmptyGraph);
+    }
+
+    let max_degrees = all_distances.values()
+        .map(|distances| *distances.values().max().unwrap_or(&0))
         .collect::<Vec<_>>();
 
-    *max_degrees.iter().max().unwrap_or(&0)
+    Ok(*max_degrees.iter().max().unwrap_or(&0))
 }
 
 // Calculate the average of the maximum degree of separation for each node.
-pub fn calculate_average_max_degree(adjacency_list: &HashMap<i32, HashSet<i32>>) -> f64 {
-    let max_degrees = adjacency_list.keys()
-        .map(|&city| {
-            let distances = bfs(&adjacency_list, city);
-            *distances.values().max().unwrap_or(&0)
-        })
+pub fn calculate_average_max_degree<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Result<f64, GraphError> {
+    calculate_average_max_degree_from_distances(&compute_all_distances(adjacency_list))
+}
+
+// `calculate_average_max_degree` from a precomputed `compute_all_distances` map, with the same result and no extra BFS passes.
+pub fn calculate_average_max_degree_from_distances<N>(all_distances: &HashMap<N, HashMap<N, i32>>) -> Result<f64, GraphError> {
+    let max_degrees = all_distances.values()
+        .map(|distances| *distances.values().max().unwrap_or(&0))
         .collect::<Vec<_>>();
 
-    max_degrees.iter().sum::<i32>() as f64 / max_degrees.len() as f64
+    // An empty graph has no eccentricities to average.
+    if max_degrees.is_empty() {
+        return Err(GraphError::EmptyGraph);
+    }
+
+    Ok(max_degrees.iter().sum::<i32>() as f64 / max_degrees.len() as f64)
 }
 
-// Connected components are groups of nodes where each node is reachable from any other node in the same group.
-pub fn calculate_connected_components(adjacency_list: &HashMap<i32, HashSet<i32>>) -> usize {
-    // Use BFS to find the maximum degree of separation for each node.
-    // We then collect unique maximum degrees, which corresponds to separate connected components.
+// Whether the whole graph is a single connected component, checked with one BFS from an arbitrary node
+// instead of finding every component. An empty graph has no components, so it isn't connected.
+pub fn is_connected<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> bool {
     adjacency_list.keys()
-        .map(|&city| {
-            let distances = bfs(&adjacency_list, city);
-            *distances.values().max().unwrap_or(&0)
-        })
-        .collect::<HashSet<_>>()
-        .len()
+        .next()
+        .is_some_and(|&city| bfs(adjacency_list, city).len() == adjacency_list.len())
+}
+
+// How many other nodes each node can reach, i.e. the size of its BFS tree minus itself. In an undirected graph this is
+// the size of the node's component minus one, so nodes of the giant component stand out; in a directed graph
+// it follows edge directions.
+pub fn reachable_counts<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> HashMap<N, usize> {
+    adjacency_list.keys()
+        .map(|&city| (city, bfs(adjacency_list, city).len() - 1))
+        .collect()
+}
+
+// Connected components are groups of nodes where each node is reachable from any other node in the same group.
+// Returns the node set of every component, sorted by size with the largest component first.
+pub fn connected_components<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Vec<HashSet<N>> {
+    let mut visited: HashSet<N> = HashSet::new();
+    let mut components: Vec<HashSet<N>> = Vec::new();
+
+    // Every node that hasn't been reached by an earlier BFS seeds a new component.
+    for &city in adjacency_list.keys() {
+        if visited.contains(&city) {
+            continue;
+        }
+        let component: HashSet<N> = bfs(adjacency_list, city).keys().copied().collect();
+        visited.extend(component.iter().copied());
+        components.push(component);
+    }
+
+    components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+    components
+}
+
+// The number of connected components in the graph.
+pub fn calculate_connected_components<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> usize {
+    connected_components(adjacency_list).len()
+}
+
+// Extract the adjacency list of the largest connected component, dropping every other node and edge.
+// This gives a connected subgraph that the separation metrics can be applied to without mixing in other components.
+pub fn largest_connected_component<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> HashMap<N, HashSet<N>> {
+    let Some(largest) = connected_components(adjacency_list).into_iter().next() else {
+        return HashMap::new();
+    };
+
+    adjacency_list.iter()
+        .filter(|(city, _)| largest.contains(*city))
+        .map(|(&city, neighbors)| (city, neighbors.clone()))
+        .collect()
+}
+
+// The diameter of a single component: the longest shortest path between any two of its nodes.
+fn component_diameter<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>, component: &HashSet<N>) -> i32 {
+    component.iter()
+        .map(|&city| *bfs(adjacency_list, city).values().max().unwrap_or(&0))
+        .max()
+        .unwrap_or(0)
+}
+
+// The diameter is the longest shortest path within the largest connected component.
+// Unlike `calculate_max_degree_of_separation`, pairs in other (smaller) components are ignored.
+pub fn calculate_diameter<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Result<i32, GraphError> {
+    connected_components(adjacency_list)
+        .first()
+        .map(|largest| component_diameter(adjacency_list, largest))
+        .ok_or(GraphError::EmptyGraph)
+}
+
+// The two endpoints of a diametral pair of the largest connected component and their distance, `(a, b, diameter)`,
+// i.e. which two cities are farthest apart. When several pairs are equally far apart, the smallest pair is returned,
+// with `a < b`. A single-node component gives `(node, node, 0)`. Returns `None` for an empty graph.
+pub fn diameter_path<N: Eq + Hash + Copy + Ord>(adjacency_list: &HashMap<N, HashSet<N>>) -> Option<(N, N, i32)> {
+    let largest = connected_components(adjacency_list).into_iter().next()?;
+
+    let mut best: Option<(N, N, i32)> = None;
+    for &city in &largest {
+        for (target, distance) in bfs(adjacency_list, city) {
+            let (a, b) = (city.min(target), city.max(target));
+            let is_better = best.is_none_or(|(best_a, best_b, best_distance)| {
+                distance > best_distance || (distance == best_distance && (a, b) < (best_a, best_b))
+            });
+            if is_better {
+                best = Some((a, b, distance));
+            }
+        }
+    }
+    best
+}
+
+// A fast lower bound on `calculate_diameter` using the double-sweep heuristic: BFS from a node of the largest component
+// to find the farthest node `u`, then BFS from `u`; the farthest distance found is the estimate. It is exact on trees
+// (and often on road-like graphs) while costing two BFS passes instead of one per node. Returns 0 for an empty graph.
+pub fn approximate_diameter<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> i32 {
+    let Some(&start) = connected_components(adjacency_list).first().and_then(|largest| largest.iter().next()) else {
+        return 0;
+    };
+
+    let farthest = |from: N| bfs(adjacency_list, from).into_iter().max_by_key(|&(_, distance)| distance).unwrap_or((from, 0));
+    let (u, _) = farthest(start);
+    farthest(u).1
+}
+
+// The diameter of every connected component, ordered from the largest component to the smallest.
+pub fn calculate_component_diameters<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Vec<i32> {
+    connected_components(adjacency_list)
+        .iter()
+        .map(|component| component_diameter(adjacency_list, component))
+        .collect()
+}
+
+// The eccentricity of a node is its maximum distance to any node it can reach.
+// Unreachable nodes are ignored, so an isolated node has eccentricity 0. Returns `None` if the node isn't in the graph.
+pub fn eccentricity<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>, node: N) -> Option<i32> {
+    if !adjacency_list.contains_key(&node) {
+        return None;
+    }
+    bfs(adjacency_list, node).values().max().copied()
+}
+
+// The eccentricity of every node, from one BFS per node rather than a separate `eccentricity` call for each.
+// As with `eccentricity`, only nodes reachable from each source count.
+pub fn all_eccentricities<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> HashMap<N, i32> {
+    all_eccentricities_from_distances(&compute_all_distances(adjacency_list))
+}
+
+// `all_eccentricities` from a precomputed `compute_all_distances` map, with the same result; unreachable pairs are ignored.
+pub fn all_eccentricities_from_distances<N: Eq + Hash + Copy>(all_distances: &HashMap<N, HashMap<N, i32>>) -> HashMap<N, i32> {
+    all_distances.iter()
+        .map(|(&city, distances)| (city, distances.values().max().copied().unwrap_or(0)))
+        .collect()
+}
+
+// The radius is the minimum eccentricity over the nodes of the largest connected component.
+// Restricting to the largest component keeps small or isolated components from pulling the radius down.
+// Returns `None` for an empty graph.
+pub fn radius<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Option<i32> {
+    let largest = connected_components(adjacency_list).into_iter().next()?;
+    largest.iter()
+        .filter_map(|&city| eccentricity(adjacency_list, city))
+        .min()
+}
+
+// The center of the graph is the set of nodes whose eccentricity equals the radius, i.e. the best hub locations.
+// Like `radius`, only the largest connected component is considered. The nodes are returned in ascending order.
+pub fn graph_center<N: Eq + Hash + Copy + Ord>(adjacency_list: &HashMap<N, HashSet<N>>) -> Vec<N> {
+    let Some(largest) = connected_components(adjacency_list).into_iter().next() else {
+        return Vec::new();
+    };
+
+    let eccentricities: Vec<(N, i32)> = largest.iter()
+        .filter_map(|&city| Some((city, eccentricity(adjacency_list, city)?)))
+        .collect();
+    let min_eccentricity = eccentricities.iter().map(|&(_, ecc)| ecc).min().unwrap_or(0);
+
+    let mut center: Vec<N> = eccentricities.into_iter()
+        .filter(|&(_, ecc)| ecc == min_eccentricity)
+        .map(|(city, _)| city)
+        .collect();
+    center.sort();
+    center
 }
 
 // This is the average number of edges on the shortest path between pairs of nodes.
-pub fn calculate_average_shortest_path_length(adjacency_list: &HashMap<i32, HashSet<i32>>) -> f64 {
-    // Use BFS to calculate the total length of shortest paths and the number of such paths.
-    let (total_length, total_paths) = adjacency_list.keys().fold((0, 0), |(total_length, total_paths), &city| {
-        let distances = bfs(&adjacency_list, city);
+pub fn calculate_average_shortest_path_length<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Result<f64, GraphError> {
+    calculate_average_shortest_path_length_from_distances(&compute_all_distances(adjacency_list))
+}
+
+// `calculate_average_shortest_path_length` from a precomputed `compute_all_distances` map, with the same result and no extra BFS passes.
+pub fn calculate_average_shortest_path_length_from_distances<N>(all_distances: &HashMap<N, HashMap<N, i32>>) -> Result<f64, GraphError> {
+    if all_distances.is_empty() {
+        return Err(GraphError::EmptyGraph);
+    }
+
+    // Calculate the total length of shortest paths and the number of such paths.
+    // The total is accumulated as an i64 since summing every pair's distance overflows an i32 on large graphs.
+    let (total_length, total_paths) = all_distances.values().fold((0i64, 0i64), |(total_length, total_paths), distances| {
         distances.values().fold((total_length, total_paths), |(length, paths), &distance| {
             if distance > 0 {
-                (length + distance, paths + 1)
+                (length + distance as i64, paths + 1)
             } else {
                 (length, paths)
             }
         })
     });
 
-    total_length as f64 / total_paths as f64
+    // Without any paths (e.g. only isolated nodes) the average is defined as 0.
+    if total_paths == 0 {
+        return Ok(0.0);
+    }
+
+    Ok(total_length as f64 / total_paths as f64)
+}
+
+// The Wiener index: the sum of the shortest path lengths over all unordered pairs of nodes, each pair counted once.
+// Pairs in different components have no path and are skipped. It is the raw total behind the average path length.
+pub fn wiener_index<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> i64 {
+    let total: i64 = adjacency_list.keys()
+        .map(|&city| bfs(adjacency_list, city).values().map(|&distance| distance as i64).sum::<i64>())
+        .sum();
+    // Every pair was reached once from each end.
+    total / 2
+}
+
+// The per-component average path lengths, keyed by component index, and their pair-weighted overall average.
+pub type ComponentAveragePathLengths = (HashMap<usize, f64>, f64);
+
+// The average shortest path length within each connected component, keyed by the component's index in
+// `connected_components` order (0 is the largest), plus an overall average weighted by each component's number of
+// connected pairs. Components of a single node have no pairs and an average of 0.0. The weighted overall average
+// equals `calculate_average_shortest_path_length`; the per-component values show how the fragments differ.
+pub fn calculate_component_average_path_lengths<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Result<ComponentAveragePathLengths, GraphError> {
+    let components = connected_components(adjacency_list);
+    if components.is_empty() {
+        return Err(GraphError::EmptyGraph);
+    }
+
+    let mut averages: HashMap<usize, f64> = HashMap::new();
+    let (mut total_length, mut total_pairs) = (0i64, 0i64);
+    for (component_id, component) in components.iter().enumerate() {
+        let (length, pairs) = component.iter().fold((0i64, 0i64), |(length, pairs), &city| {
+            bfs(adjacency_list, city).values()
+                .filter(|&&distance| distance > 0)
+                .fold((length, pairs), |(length, pairs), &distance| (length + distance as i64, pairs + 1))
+        });
+        averages.insert(component_id, if pairs == 0 { 0.0 } else { length as f64 / pairs as f64 });
+        total_length += length;
+        total_pairs += pairs;
+    }
+
+    let overall = if total_pairs == 0 { 0.0 } else { total_length as f64 / total_pairs as f64 };
+    Ok((averages, overall))
+}
+
+// The weighted counterpart of the average shortest path length: the mean Dijkstra distance over all ordered pairs
+// of distinct nodes, i.e. the average real travel distance rather than the hop count.
+// Unreachable pairs are excluded. Returns 0.0 if there are no such pairs, and `GraphError::EmptyGraph` without nodes.
+pub fn calculate_weighted_average_path_length(adj_list: &HashMap<i32, HashMap<i32, f64>>) -> Result<f64, GraphError> {
+    if adj_list.is_empty() {
+        return Err(GraphError::EmptyGraph);
+    }
+
+    let mut total_length = 0.0;
+    let mut total_paths = 0;
+
+    for &city in adj_list.keys() {
+        for (&target, &distance) in &dijkstra(adj_list, city) {
+            if target != city {
+                total_length += distance;
+                total_paths += 1;
+            }
+        }
+    }
+
+    if total_paths == 0 {
+        return Ok(0.0);
+    }
+
+    Ok(total_length / total_paths as f64)
 }
 
 // This returns a distribution of the shortest path lengths between nodes, the degree with the maximum percentage, and the corresponding percentage.
-pub fn calculate_normalized_separation_distribution(adjacency_list: &HashMap<i32, HashSet<i32>>) -> (HashMap<i32, f64>, i32, f64) {
+// If several degrees share the maximum percentage, the smallest of them is returned.
+// The distribution is ordered by degree so that printed output is identical from run to run.
+pub fn calculate_normalized_separation_distribution<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Result<(BTreeMap<i32, f64>, i32, f64), GraphError> {
+    calculate_normalized_separation_distribution_from_distances(&compute_all_distances(adjacency_list))
+}
+
+// `calculate_normalized_separation_distribution` from a precomputed `compute_all_distances` map, with the same result and no extra BFS passes.
+pub fn calculate_normalized_separation_distribution_from_distances<N>(all_distances: &HashMap<N, HashMap<N, i32>>) -> Result<(BTreeMap<i32, f64>, i32, f64), GraphError> {
+    if all_distances.is_empty() {
+        return Err(GraphError::EmptyGraph);
+    }
+
     let mut total_paths = 0;
     let mut separation_distribution: HashMap<i32, i32> = HashMap::new();
 
-    // Use BFS to find all path lengths and record the frequency of each path length.
-    for &city in adjacency_list.keys() {
-        let distances = bfs(&adjacency_list, city);
+    // Record the frequency of each path length.
+    for distances in all_distances.values() {
         for &length in distances.values() {
             if length > 0 { // Exclude the path to itself
                 *separation_distribution.entry(length).or_insert(0) += 1;
@@ -97,58 +680,166 @@ pub fn calculate_normalized_separation_distribution(adjacency_list: &HashMap<i32
         }
     }
 
+    // Without any paths (e.g. a graph of only self-loops) there is nothing to normalize.
+    if total_paths == 0 {
+        return Ok((BTreeMap::new(), 0, 0.0));
+    }
+
     // Normalize the separation distribution so it sums to 1.
-    let normalized_separation_distribution: HashMap<i32, f64> = separation_distribution
+    let normalized_separation_distribution: BTreeMap<i32, f64> = separation_distribution
         .iter()
         .map(|(&degree, &count)| (degree, count as f64 / total_paths as f64))
         .collect();
 
-    // Find the path length that occurs most frequently.
+    // Find the path length that occurs most frequently. Tied lengths have the same count and so the same percentage;
+    // the smallest of them is reported so the result is the same on every run.
     let (&degree_with_max_percentage, &max_percentage) = normalized_separation_distribution
         .iter()
-        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+        .max_by(|a, b| a.1.total_cmp(b.1).then(b.0.cmp(a.0)))
         .unwrap_or((&0, &0.0));
 
-    (normalized_separation_distribution, degree_with_max_percentage, max_percentage)
+    Ok((normalized_separation_distribution, degree_with_max_percentage, max_percentage))
+}
+
+// The weighted counterpart of the separation distribution: the share of pairs whose Dijkstra travel distance falls
+// in each bucket of width `bucket_size`. Bucket `k` covers distances in [k * bucket_size, (k + 1) * bucket_size).
+// Unreachable pairs are excluded. Returns an empty distribution if there are no paths or `bucket_size` isn't positive.
+pub fn weighted_separation_distribution(adj_list: &HashMap<i32, HashMap<i32, f64>>, bucket_size: f64) -> BTreeMap<i32, f64> {
+    if bucket_size.is_nan() || bucket_size <= 0.0 {
+        return BTreeMap::new();
+    }
+    let mut bucket_counts: BTreeMap<i32, usize> = BTreeMap::new();
+    let mut total_paths = 0;
+
+    for &city in adj_list.keys() {
+        for (&target, &distance) in &dijkstra(adj_list, city) {
+            if target != city {
+                *bucket_counts.entry((distance / bucket_size).floor() as i32).or_insert(0) += 1;
+                total_paths += 1;
+            }
+        }
+    }
+
+    bucket_counts.into_iter()
+        .map(|(bucket, count)| (bucket, count as f64 / total_paths as f64))
+        .collect()
 }
 
 // This gives us an idea of the graph's connectivity and its variance.
-pub fn calculate_mean_and_std_dev(adjacency_list: &HashMap<i32, HashSet<i32>>) -> (f64, f64) {
-    let mut all_distances = Vec::new();
+pub fn calculate_mean_and_std_dev<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>) -> Result<(f64, f64), GraphError> {
+    calculate_mean_and_std_dev_from_distances(&compute_all_distances(adjacency_list))
+}
+
+// `calculate_mean_and_std_dev` from a precomputed `compute_all_distances` map, with the same result and no extra BFS passes.
+pub fn calculate_mean_and_std_dev_from_distances<N>(all_distances: &HashMap<N, HashMap<N, i32>>) -> Result<(f64, f64), GraphError> {
+    if all_distances.is_empty() {
+        return Err(GraphError::EmptyGraph);
+    }
+
+    let mut path_lengths = Vec::new();
 
     // Collect all distances between nodes
-    for &city in adjacency_list.keys() {
-        let distances = bfs(adjacency_list, city);
+    for distances in all_distances.values() {
         for &distance in distances.values() {
             if distance > 0 { // Exclude the distance to the node itself
-                all_distances.push(distance);
+                path_lengths.push(distance);
             }
         }
     }
 
+    // Without any paths (e.g. only isolated nodes) both statistics are defined as 0.
+    if path_lengths.is_empty() {
+        return Ok((0.0, 0.0));
+    }
+
     // Calculate the mean of all path lengths.
-    let mean: f64 = all_distances.iter().sum::<i32>() as f64 / all_distances.len() as f64;
+    let mean: f64 = path_lengths.iter().map(|&distance| distance as i64).sum::<i64>() as f64 / path_lengths.len() as f64;
     
     // Calculate the variance and then the standard deviation to measure how much the path lengths vary.
-    let variance: f64 = all_distances.iter()
+    let variance: f64 = path_lengths.iter()
         .map(|&distance| {
             let diff = distance as f64 - mean;
             diff * diff
         })
-        .sum::<f64>() / all_distances.len() as f64;
+        .sum::<f64>() / path_lengths.len() as f64;
 
     // Calculate the standard deviation
     let std_dev = variance.sqrt();
 
-    (mean, std_dev)
+    Ok((mean, std_dev))
 }
 
-// Create a sample graph for testing purposes.
+// The requested percentiles (0-100, e.g. 50.0 for the median) of all pairwise shortest path lengths, using the
+// nearest-rank method. Each percentile is paired with its path length, in the order requested.
+// Every percentile is 0 if there are no paths.
+pub fn path_length_percentiles<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>, percentiles: &[f64]) -> Vec<(f64, i32)> {
+    let mut path_lengths: Vec<i32> = compute_all_distances(adjacency_list)
+        .values()
+        .flat_map(|distances| distances.values().copied().filter(|&distance| distance > 0))
+        .collect();
+    path_lengths.sort_unstable();
+
+    percentiles.iter()
+        .map(|&percentile| {
+            if path_lengths.is_empty() {
+                return (percentile, 0);
+            }
+            let rank = (percentile / 100.0 * path_lengths.len() as f64).ceil() as usize;
+            (percentile, path_lengths[rank.clamp(1, path_lengths.len()) - 1])
+        })
+        .collect()
+}
+
+// The mean shortest path length after discarding the shortest and longest `trim_fraction` of all pairwise distances
+// (e.g. 0.1 drops the bottom and top 10%), so a few very long detours don't skew the average. A fraction of 0 gives the
+// plain mean; at least one distance is always kept. Returns 0.0 if there are no paths.
+pub fn trimmed_mean_separation<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>, trim_fraction: f64) -> f64 {
+    let mut path_lengths: Vec<i32> = compute_all_distances(adjacency_list)
+        .values()
+        .flat_map(|distances| distances.values().copied().filter(|&distance| distance > 0))
+        .collect();
+    if path_lengths.is_empty() {
+        return 0.0;
+    }
+    path_lengths.sort_unstable();
+
+    let trim_fraction = if trim_fraction.is_nan() { 0.0 } else { trim_fraction.clamp(0.0, 0.5) };
+    let trimmed = ((path_lengths.len() as f64 * trim_fraction).floor() as usize).min((path_lengths.len() - 1) / 2);
+    let kept = &path_lengths[trimmed..path_lengths.len() - trimmed];
+    kept.iter().map(|&distance| distance as i64).sum::<i64>() as f64 / kept.len() as f64
+}
+
+// The fraction of all reachable pairs of distinct nodes that are at most `k` hops apart,
+// e.g. `k = 6` answers "what share of cities are within six degrees of separation". Returns 0.0 if there are no pairs.
+pub fn fraction_within_degrees<N: Eq + Hash + Copy>(adjacency_list: &HashMap<N, HashSet<N>>, k: i32) -> f64 {
+    let mut total_paths = 0;
+    let mut paths_within = 0;
+
+    for &city in adjacency_list.keys() {
+        for &distance in bfs(adjacency_list, city).values() {
+            if distance > 0 {
+                total_paths += 1;
+                if distance <= k {
+                    paths_within += 1;
+                }
+            }
+        }
+    }
+
+    if total_paths == 0 {
+        return 0.0;
+    }
+
+    paths_within as f64 / total_paths as f64
+}
+
+// Create a sample graph for testing purposes: the path 1 - 2 - 3. Many tests rely on its exact distances, so it
+// stays a path; use `build_cyclic_sample` when a cycle is needed.
 #[cfg(test)]
 pub fn build_sample_network() -> HashMap<i32, HashSet<i32>> {
     let mut adjacency_list: HashMap<i32, HashSet<i32>> = HashMap::new();
 
-    // Define a small graph manually by adding edges. This graph is a simple triangle connecting nodes 1, 2, and 3.
+    // Define a small graph manually by adding each edge in both directions. This graph is a path, not a triangle:
     // 1 - 2 - 3
     adjacency_list.entry(1).or_default().insert(2);
     adjacency_list.entry(2).or_default().insert(1);
@@ -156,4 +847,52 @@ pub fn build_sample_network() -> HashMap<i32, HashSet<i32>> {
     adjacency_list.entry(3).or_default().insert(2);
 
     adjacency_list
-}
\ No newline at end of file
+}
+
+// Create a small weighted graph for testing purposes.
+// 1 -(1.0)- 2 -(2.0)- 3, plus a direct but longer 1 -(5.0)- 3 road.
+#[cfg(test)]
+pub fn build_weighted_sample_network() -> HashMap<i32, HashMap<i32, f64>> {
+    let mut adjacency_list: HashMap<i32, HashMap<i32, f64>> = HashMap::new();
+
+    for &(a, b, weight) in &[(1, 2, 1.0), (2, 3, 2.0), (1, 3, 5.0)] {
+        adjacency_list.entry(a).or_default().insert(b, weight);
+        adjacency_list.entry(b).or_default().insert(a, weight);
+    }
+
+    adjacency_list
+}
+
+// Build an undirected adjacency list from a slice of edges. Shared by the fixtures below and the unit tests.
+#[cfg(test)]
+pub fn build_network_from_edges(edges: &[(i32, i32)]) -> HashMap<i32, HashSet<i32>> {
+    let mut adjacency_list: HashMap<i32, HashSet<i32>> = HashMap::new();
+
+    for &(a, b) in edges {
+        adjacency_list.entry(a).or_default().insert(b);
+        adjacency_list.entry(b).or_default().insert(a);
+    }
+
+    adjacency_list
+}
+
+// Create a test graph with two components: the path 1 - 2 - 3 and the separate edge 4 - 5.
+#[cfg(test)]
+pub fn build_disconnected_sample() -> HashMap<i32, HashSet<i32>> {
+    build_network_from_edges(&[(1, 2), (2, 3), (4, 5)])
+}
+
+// Create a test graph that is a single cycle: 1 - 2 - 3 - 4 - 5 - 1.
+#[cfg(test)]
+pub fn build_cyclic_sample() -> HashMap<i32, HashSet<i32>> {
+    build_network_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (5, 1)])
+}
+
+// Create a star test graph: hub 0 connected to each of the leaves 1..=n, which aren't connected to each other.
+#[cfg(test)]
+pub fn build_star_sample(n: i32) -> HashMap<i32, HashSet<i32>> {
+    let edges: Vec<(i32, i32)> = (1..=n).map(|leaf| (0, leaf)).collect();
+    let mut adjacency_list = build_network_from_edges(&edges);
+    adjacency_list.entry(0).or_default();
+    adjacency_list
+}
@@ -0,0 +1,446 @@
+use crate::separation_deg::GraphError;
+use hashbrown::{HashMap, HashSet};
+use std::collections::VecDeque;
+
+// The k-core is what's left after repeatedly removing nodes with fewer than `k` neighbors.
+// Removing a node lowers its neighbors' degrees, so they are re-checked and may be removed in turn.
+// This strips away dead-end stubs and leaves the dense core of the network.
+pub fn k_core(adj_list: &HashMap<i32, HashSet<i32>>, k: usize) -> HashMap<i32, HashSet<i32>> {
+    let mut degrees: HashMap<i32, usize> = adj_list.iter()
+        .map(|(&node, neighbors)| (node, neighbors.iter().filter(|&&neighbor| neighbor != node).count()))
+        .collect();
+    let mut to_remove: Vec<i32> = degrees.iter().filter(|(_, &degree)| degree < k).map(|(&node, _)| node).collect();
+    let mut removed: HashSet<i32> = to_remove.iter().copied().collect();
+
+    while let Some(node) = to_remove.pop() {
+        for &neighbor in &adj_list[&node] {
+            if removed.contains(&neighbor) {
+                continue;
+            }
+            if let Some(degree) = degrees.get_mut(&neighbor) {
+                *degree -= 1;
+                if *degree < k {
+                    removed.insert(neighbor);
+                    to_remove.push(neighbor);
+                }
+            }
+        }
+    }
+
+    adj_list.iter()
+        .filter(|(node, _)| !removed.contains(*node))
+        .map(|(&node, neighbors)| (node, neighbors.iter().filter(|neighbor| !removed.contains(*neighbor)).copied().collect()))
+        .collect()
+}
+
+// The induced subgraph on a set of nodes: only the given nodes (that exist in the graph) and the edges between them.
+// Edges leading to nodes outside the set are dropped.
+pub fn subgraph(adj_list: &HashMap<i32, HashSet<i32>>, nodes: &HashSet<i32>) -> HashMap<i32, HashSet<i32>> {
+    adj_list.iter()
+        .filter(|(node, _)| nodes.contains(*node))
+        .map(|(&node, neighbors)| (node, neighbors.intersection(nodes).copied().collect()))
+        .collect()
+}
+
+// A copy of the graph with `node` and every edge touching it removed, e.g. to simulate losing a city.
+pub fn remove_node(adj_list: &HashMap<i32, HashSet<i32>>, node: i32) -> HashMap<i32, HashSet<i32>> {
+    adj_list.iter()
+        .filter(|(&other, _)| other != node)
+        .map(|(&other, neighbors)| (other, neighbors.iter().copied().filter(|&neighbor| neighbor != node).collect()))
+        .collect()
+}
+
+// Discovery times, low-link values and DFS tree edges from a depth-first search over every component.
+// `low[node]` is the earliest discovery time reachable from the node's DFS subtree using at most one back edge.
+struct LowLink {
+    discovery: HashMap<i32, usize>,
+    low: HashMap<i32, usize>,
+    tree_edges: Vec<(i32, i32)>,
+    roots: HashSet<i32>,
+}
+
+// Run the low-link DFS with an explicit stack, so long chains of roads can't overflow the call stack.
+fn low_link(adj_list: &HashMap<i32, HashSet<i32>>) -> LowLink {
+    let mut discovery: HashMap<i32, usize> = HashMap::new();
+    let mut low: HashMap<i32, usize> = HashMap::new();
+    let mut tree_edges: Vec<(i32, i32)> = Vec::new();
+    let mut roots: HashSet<i32> = HashSet::new();
+    let mut timer = 0;
+
+    for &root in adj_list.keys() {
+        if discovery.contains_key(&root) {
+            continue;
+        }
+        roots.insert(root);
+        discovery.insert(root, timer);
+        low.insert(root, timer);
+        timer += 1;
+
+        // Each frame holds a node, its DFS parent, and the neighbors still to be explored.
+        let mut stack: Vec<(i32, Option<i32>, Vec<i32>)> = vec![(root, None, adj_list[&root].iter().copied().collect())];
+        while let Some((node, parent, remaining)) = stack.last_mut() {
+            let (node, parent) = (*node, *parent);
+            if let Some(next) = remaining.pop() {
+                if Some(next) == parent {
+                    continue;
+                }
+                if let Some(&next_discovery) = discovery.get(&next) {
+                    // Back edge to an ancestor (or an already finished node in this subtree).
+                    let node_low = low.get_mut(&node).unwrap();
+                    *node_low = (*node_low).min(next_discovery);
+                } else {
+                    discovery.insert(next, timer);
+                    low.insert(next, timer);
+                    timer += 1;
+                    let next_neighbors = adj_list.get(&next).map(|neighbors| neighbors.iter().copied().collect()).unwrap_or_default();
+                    stack.push((next, Some(node), next_neighbors));
+                }
+            } else {
+                stack.pop();
+                if let Some(parent) = parent {
+                    let node_low = low[&node];
+                    let parent_low = low.get_mut(&parent).unwrap();
+                    *parent_low = (*parent_low).min(node_low);
+                    tree_edges.push((parent, node));
+                }
+            }
+        }
+    }
+
+    LowLink { discovery, low, tree_edges, roots }
+}
+
+// Articulation points (cut vertices) are nodes whose removal increases the number of connected components.
+// A DFS root is one if it has two or more DFS children; any other node is one if some child's subtree
+// can't reach above it without going through it (`low[child] >= discovery[node]`).
+pub fn articulation_points(adj_list: &HashMap<i32, HashSet<i32>>) -> HashSet<i32> {
+    let LowLink { discovery, low, tree_edges, roots } = low_link(adj_list);
+    let mut points: HashSet<i32> = HashSet::new();
+    let mut root_children: HashMap<i32, usize> = HashMap::new();
+
+    for (parent, child) in tree_edges {
+        if roots.contains(&parent) {
+            *root_children.entry(parent).or_insert(0) += 1;
+        } else if low[&child] >= discovery[&parent] {
+            points.insert(parent);
+        }
+    }
+    points.extend(root_children.into_iter().filter(|&(_, children)| children >= 2).map(|(root, _)| root));
+
+    points
+}
+
+// Bridges (cut edges) are edges whose removal disconnects their endpoints, i.e. single points of failure between regions.
+// A DFS tree edge `parent -> child` is a bridge when the child's subtree has no back edge reaching the parent or above
+// (`low[child] > discovery[parent]`). Edges are returned with the smaller id first, sorted.
+pub fn bridges(adj_list: &HashMap<i32, HashSet<i32>>) -> Vec<(i32, i32)> {
+    let LowLink { discovery, low, tree_edges, .. } = low_link(adj_list);
+
+    let mut bridges: Vec<(i32, i32)> = tree_edges.into_iter()
+        .filter(|(parent, child)| low[child] > discovery[parent])
+        .map(|(parent, child)| (parent.min(child), parent.max(child)))
+        .collect();
+    bridges.sort();
+    bridges
+}
+
+// The strongly connected components of a directed graph: maximal groups of nodes that can all reach each other,
+// e.g. a one-way road system you can drive around and get back from. Uses Tarjan's algorithm with an explicit stack,
+// like `low_link`. Each component is sorted, and components are ordered by their smallest node.
+pub fn strongly_connected_components(adj_list: &HashMap<i32, HashSet<i32>>) -> Vec<Vec<i32>> {
+    let mut index: HashMap<i32, usize> = HashMap::new();
+    let mut low: HashMap<i32, usize> = HashMap::new();
+    let mut on_stack: HashSet<i32> = HashSet::new();
+    let mut tarjan_stack: Vec<i32> = Vec::new();
+    let mut components: Vec<Vec<i32>> = Vec::new();
+    let mut timer = 0;
+
+    let mut roots: Vec<i32> = adj_list.keys().copied().collect();
+    roots.sort_unstable();
+    for root in roots {
+        if index.contains_key(&root) {
+            continue;
+        }
+        index.insert(root, timer);
+        low.insert(root, timer);
+        timer += 1;
+        tarjan_stack.push(root);
+        on_stack.insert(root);
+
+        // Each frame holds a node and the successors still to be explored.
+        let mut stack: Vec<(i32, Vec<i32>)> = vec![(root, adj_list[&root].iter().copied().collect())];
+        while let Some((node, remaining)) = stack.last_mut() {
+            let node = *node;
+            if let Some(next) = remaining.pop() {
+                if let Some(&next_index) = index.get(&next) {
+                    // Only nodes still on the stack belong to a component that isn't finished yet.
+                    if on_stack.contains(&next) {
+                        let node_low = low.get_mut(&node).unwrap();
+                        *node_low = (*node_low).min(next_index);
+                    }
+                } else {
+                    index.insert(next, timer);
+                    low.insert(next, timer);
+                    timer += 1;
+                    tarjan_stack.push(next);
+                    on_stack.insert(next);
+                    let next_successors = adj_list.get(&next).map(|successors| successors.iter().copied().collect()).unwrap_or_default();
+                    stack.push((next, next_successors));
+                }
+            } else {
+                stack.pop();
+                let node_low = low[&node];
+                if let Some((parent, _)) = stack.last() {
+                    let parent_low = low.get_mut(parent).unwrap();
+                    *parent_low = (*parent_low).min(node_low);
+                }
+
+                // A node that can't reach anything earlier is the root of a component: everything above it on the stack.
+                if node_low == index[&node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = tarjan_stack.pop() {
+                        on_stack.remove(&member);
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components.sort_unstable_by_key(|component| component[0]);
+    components
+}
+
+// A maximum matching of a bipartite graph with the declared sides `left` and `right`: as many `(left, right)` edges
+// as possible with no node used twice, e.g. assigning depots to towns. Each left node in turn looks for an augmenting
+// path with a BFS, re-matching earlier pairs along the way if that frees up a partner. Returns the pairs sorted by left
+// node. Fails with `GraphError::NodeOnBothSides` for the smallest node declared on both sides, or
+// `GraphError::EdgeWithinSide` for the first edge from either side that doesn't end on the other side. Edges between
+// nodes declared on neither side are ignored.
+pub fn maximum_bipartite_matching(adj_list: &HashMap<i32, HashSet<i32>>, left: &HashSet<i32>, right: &HashSet<i32>) -> Result<Vec<(i32, i32)>, GraphError> {
+    let sorted_neighbors = |node: &i32| {
+        let mut neighbors: Vec<i32> = adj_list.get(node).into_iter().flatten().copied().collect();
+        neighbors.sort_unstable();
+        neighbors
+    };
+    if let Some(&node) = left.intersection(right).min() {
+        return Err(GraphError::NodeOnBothSides(node));
+    }
+    let mut left_nodes: Vec<i32> = left.iter().copied().collect();
+    left_nodes.sort_unstable();
+    let mut right_nodes: Vec<i32> = right.iter().copied().collect();
+    right_nodes.sort_unstable();
+
+    for (side, other_side) in [(&left_nodes, right), (&right_nodes, left)] {
+        for node in side {
+            if let Some(neighbor) = sorted_neighbors(node).into_iter().find(|neighbor| !other_side.contains(neighbor)) {
+                return Err(GraphError::EdgeWithinSide(*node, neighbor));
+            }
+        }
+    }
+
+    let mut left_match: HashMap<i32, i32> = HashMap::new();
+    let mut right_match: HashMap<i32, i32> = HashMap::new();
+    for &start in &left_nodes {
+        // Search alternating paths: out along any edge to the right, back along a matched edge to the left.
+        let mut parents: HashMap<i32, i32> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        let mut free_right = None;
+        'search: while let Some(node) = queue.pop_front() {
+            for neighbor in sorted_neighbors(&node) {
+                if parents.contains_key(&neighbor) {
+                    continue;
+                }
+                parents.insert(neighbor, node);
+                match right_match.get(&neighbor) {
+                    Some(&partner) => queue.push_back(partner),
+                    None => {
+                        free_right = Some(neighbor);
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        // Flip the path: every right node on it takes the left node that reached it.
+        let mut next_right = free_right;
+        while let Some(right_node) = next_right {
+            let left_node = parents[&right_node];
+            right_match.insert(right_node, left_node);
+            next_right = left_match.insert(left_node, right_node);
+        }
+    }
+
+    let mut matching: Vec<(i32, i32)> = left_match.into_iter().collect();
+    matching.sort_unstable();
+    Ok(matching)
+}
+
+// A graph is bipartite if its nodes can be split into two sides with every edge going between the sides.
+// Each component is 2-colored with a BFS, and the first edge joining two nodes of the same color (an odd cycle) fails the check.
+pub fn is_bipartite(adj_list: &HashMap<i32, HashSet<i32>>) -> bool {
+    let mut colors: HashMap<i32, bool> = HashMap::new();
+
+    for &start in adj_list.keys() {
+        if colors.contains_key(&start) {
+            continue;
+        }
+        colors.insert(start, false);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            let color = colors[&node];
+            for &neighbor in adj_list.get(&node).into_iter().flatten() {
+                match colors.get(&neighbor) {
+                    Some(&neighbor_color) if neighbor_color == color => return false,
+                    Some(_) => {}
+                    None => {
+                        colors.insert(neighbor, !color);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    true
+}
+
+// Detect whether an undirected graph contains a cycle, i.e. whether it is something other than a forest.
+// During the BFS the edge back to a node's parent is expected; reaching any other already visited node closes a cycle.
+pub fn has_cycle(adj_list: &HashMap<i32, HashSet<i32>>) -> bool {
+    let mut parents: HashMap<i32, Option<i32>> = HashMap::new();
+
+    for &start in adj_list.keys() {
+        if parents.contains_key(&start) {
+            continue;
+        }
+        parents.insert(start, None);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            for &neighbor in adj_list.get(&node).into_iter().flatten() {
+                if parents[&node] == Some(neighbor) {
+                    continue;
+                }
+                if parents.contains_key(&neighbor) {
+                    return true;
+                }
+                parents.insert(neighbor, Some(node));
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    false
+}
+
+// A disjoint-set (union-find) forest over node ids, with path compression and union by size.
+#[derive(Debug, Clone, Default)]
+pub struct DisjointSet {
+    parents: HashMap<i32, i32>,
+    sizes: HashMap<i32, usize>,
+}
+
+impl DisjointSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Add a node as its own singleton set, if it isn't already tracked.
+    pub fn insert(&mut self, node: i32) {
+        if !self.parents.contains_key(&node) {
+            self.parents.insert(node, node);
+            self.sizes.insert(node, 1);
+        }
+    }
+
+    // The representative of the set containing `node`, inserting the node if it is new.
+    pub fn find(&mut self, node: i32) -> i32 {
+        self.insert(node);
+        let mut root = node;
+        while self.parents[&root] != root {
+            root = self.parents[&root];
+        }
+        // Point every node on the way directly at the root.
+        let mut current = node;
+        while current != root {
+            let next = self.parents[&current];
+            self.parents.insert(current, root);
+            current = next;
+        }
+        root
+    }
+
+    // Merge the sets containing `a` and `b`. Returns false if they were already in the same set.
+    pub fn union(&mut self, a: i32, b: i32) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+        let (small, large) = if self.sizes[&root_a] < self.sizes[&root_b] { (root_a, root_b) } else { (root_b, root_a) };
+        self.parents.insert(small, large);
+        let small_size = self.sizes.remove(&small).unwrap_or(0);
+        *self.sizes.get_mut(&large).unwrap() += small_size;
+        true
+    }
+
+    // The number of disjoint sets. Only set representatives keep a size, so this is just the number of sizes.
+    pub fn set_count(&self) -> usize {
+        self.sizes.len()
+    }
+}
+
+// Tracks the number of connected components while edges arrive one at a time, e.g. from a stream, without
+// recomputing components from scratch after each edge. Backed by a `DisjointSet`, so each update is nearly O(1).
+#[derive(Debug, Clone, Default)]
+pub struct ConnectivityTracker {
+    sets: DisjointSet,
+}
+
+impl ConnectivityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Add a node with no edges yet, which counts as a component of its own.
+    pub fn add_node(&mut self, node: i32) {
+        self.sets.insert(node);
+    }
+
+    // Add an undirected edge, adding either endpoint if it is new. Returns true if the edge merged two components.
+    pub fn add_edge(&mut self, a: i32, b: i32) -> bool {
+        self.sets.union(a, b)
+    }
+
+    // Whether two nodes are in the same component. Nodes that haven't been added are only connected to themselves.
+    pub fn connected(&mut self, a: i32, b: i32) -> bool {
+        a == b || (self.sets.parents.contains_key(&a) && self.sets.parents.contains_key(&b) && self.sets.find(a) == self.sets.find(b))
+    }
+
+    // The number of connected components among the nodes seen so far.
+    pub fn num_components(&self) -> usize {
+        self.sets.set_count()
+    }
+}
+
+// The minimum spanning tree (or forest, for a disconnected graph) of a weighted graph, using Kruskal's algorithm:
+// edges are taken cheapest first and kept only if they join two different trees.
+// Returns the chosen edges as `(smaller id, larger id, weight)` in the order they were chosen. NaN weights are ignored.
+pub fn minimum_spanning_tree(adj_list: &HashMap<i32, HashMap<i32, f64>>) -> Vec<(i32, i32, f64)> {
+    let mut edges: Vec<(i32, i32, f64)> = adj_list.iter()
+        .flat_map(|(&a, neighbors)| neighbors.iter().filter(move |(&b, _)| a < b).map(move |(&b, &weight)| (a, b, weight)))
+        .filter(|(_, _, weight)| !weight.is_nan())
+        .collect();
+    edges.sort_by(|x, y| x.2.total_cmp(&y.2).then((x.0, x.1).cmp(&(y.0, y.1))));
+
+    let mut forest = DisjointSet::new();
+    edges.into_iter()
+        .filter(|&(a, b, _)| forest.union(a, b))
+        .collect()
+}